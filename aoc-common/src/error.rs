@@ -0,0 +1,215 @@
+//! Errors for malformed puzzle input and inputs with no answer.
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Bad input found while parsing.
+///
+/// Points at the offending token and says what was wanted instead. Printed
+/// like a compiler diagnostic:
+///
+/// ```text
+/// error: expected one of `A`, `B` or `C`, found `D`
+///  --> day2/input.txt:3:1
+///   |
+/// 3 | D Y
+///   | ^
+/// ```
+///
+/// Parsers only see text so they leave [`ParseError::file`] empty and the
+/// caller fills it in with [`ParseError::in_file`] (see
+/// [`crate::InputFile::parse`]).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// Where the input came from, if known.
+    pub file: Option<String>,
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column (in bytes) of the start of the bad token, starting at 1.
+    pub column: usize,
+    /// How many columns to underline.
+    pub width: usize,
+    /// The whole offending line.
+    pub text: String,
+    /// What should have been there.
+    pub expected: String,
+    /// What was there instead.
+    pub found: String,
+}
+
+impl ParseError {
+    /// `token` on the line at `index` (as counted by `enumerate`, so from 0)
+    /// isn't `expected`.
+    ///
+    /// `token` should be a slice of `line` so its column can be worked out.
+    /// Anything else is searched for in `line` and failing that the error
+    /// points at the end of the line.
+    #[must_use]
+    pub fn new(index: usize, line: &str, token: &str, expected: impl Display) -> Self {
+        let found = if token.is_empty() {
+            String::from("end of line")
+        } else {
+            format!("`{token}`")
+        };
+        Self {
+            file: None,
+            line: index + 1,
+            column: offset(line, token) + 1,
+            width: token.len().max(1),
+            text: String::from(line),
+            expected: expected.to_string(),
+            found,
+        }
+    }
+
+    /// The line at `index` ended before `expected`.
+    #[must_use]
+    pub fn end_of_line(index: usize, line: &str, expected: impl Display) -> Self {
+        Self::new(index, line, &line[line.len()..], expected)
+    }
+
+    /// The input ran out after the line at `index` while still wanting
+    /// `expected`.
+    #[must_use]
+    pub fn end_of_input(index: usize, line: &str, expected: impl Display) -> Self {
+        Self {
+            found: String::from("end of input"),
+            ..Self::end_of_line(index, line, expected)
+        }
+    }
+
+    /// Parse `token` on the line at `index` as a `T`, described to the user
+    /// as `expected`.
+    ///
+    /// # Errors
+    /// If `token` doesn't parse. The reason from [`FromStr`] is kept.
+    pub fn parse<T>(
+        index: usize,
+        line: &str,
+        token: &str,
+        expected: impl Display,
+    ) -> Result<T, Self>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        token.parse::<T>().map_err(|e| {
+            let err = Self::new(index, line, token, expected);
+            if token.is_empty() {
+                err
+            } else {
+                Self {
+                    found: format!("{} ({e})", err.found),
+                    ..err
+                }
+            }
+        })
+    }
+
+    /// This error with the file it came from filled in.
+    #[must_use]
+    pub fn in_file(self, file: impl Display) -> Self {
+        Self {
+            file: Some(file.to_string()),
+            ..self
+        }
+    }
+}
+
+// Byte offset of `token` within `line`.
+fn offset(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let tok = token.as_ptr() as usize;
+    if tok >= start && tok + token.len() <= start + line.len() {
+        return tok - start;
+    }
+    if token.is_empty() {
+        return line.len();
+    }
+    line.find(token).unwrap_or(line.len())
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "error: expected {}, found {}", self.expected, self.found)?;
+        writeln!(
+            f,
+            "{gutter}--> {}:{}:{}",
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width)
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The input parsed fine but there's no answer to be had from it. Too few
+/// elves to pick 3 from, a crane told to lift from an empty stack, a
+/// valley with no way through, that sort of thing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NoAnswer {
+    /// Why not.
+    pub reason: String,
+}
+
+impl NoAnswer {
+    /// No answer because of `reason`.
+    #[must_use]
+    pub fn new(reason: impl Display) -> Self {
+        Self {
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl Display for NoAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no answer: {}", self.reason)
+    }
+}
+
+impl std::error::Error for NoAnswer {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_at_token() {
+        let line = "A Q";
+        let e = ParseError::new(2, line, &line[2..], "one of `X`, `Y` or `Z`").in_file("in.txt");
+        assert_eq!((e.line, e.column, e.width), (3, 3, 1));
+        assert_eq!(
+            e.to_string(),
+            "error: expected one of `X`, `Y` or `Z`, found `Q`\n \
+             --> in.txt:3:3\n  |\n3 | A Q\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn missing_token() {
+        let e = ParseError::end_of_line(9, "move 1 from", "a stack number");
+        assert_eq!((e.line, e.column), (10, 12));
+        assert_eq!(e.found, "end of line");
+        assert!(e
+            .to_string()
+            .contains("\n10 | move 1 from\n   |            ^"));
+    }
+
+    #[test]
+    fn bad_number() {
+        let line = "1-x,3-4";
+        let e = ParseError::parse::<usize>(0, line, &line[2..3], "a number").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 3));
+        assert_eq!(e.found, "`x` (invalid digit found in string)");
+    }
+}
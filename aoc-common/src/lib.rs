@@ -0,0 +1,511 @@
+//! Shared helpers for the advent 2022 day binaries.
+//!
+//! Every day takes the same `--filename`/`--debug`/`--format`/`--part` arguments,
+//! loads its input the same way and reports answers the same way so those
+//! live here instead of being repeated 25 times.
+use color_eyre::eyre::{Result, WrapErr};
+use serde::{Serialize, Serializer};
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+mod error;
+
+pub use error::{NoAnswer, ParseError};
+
+/// Environment variable naming a directory of puzzle inputs.
+///
+/// When set relative filenames are looked up in `$AOC_INPUT_DIR/<day>/`
+/// instead of the day's crate directory.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+/// Filename meaning "read the input from stdin".
+pub const STDIN: &str = "-";
+
+/// Arguments shared by every day. Flatten into a day's own `Args`.
+// The group id defaults to the struct name which would collide with the
+// day's own `Args` it gets flattened into.
+#[derive(Clone, Debug, clap::Args)]
+#[group(id = "common")]
+pub struct Args {
+    /// Input file. Relative paths are resolved against the day's crate
+    /// directory (or `$AOC_INPUT_DIR/<day>` if set). Use `-` for stdin.
+    #[arg(long, default_value_t = String::from("input.txt"))]
+    pub filename: String,
+
+    /// Print debugging output.
+    #[arg(long, default_value_t = false)]
+    pub debug: bool,
+
+    /// How to print answers.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+
+    /// Which parts to run. Parts not asked for aren't computed at all.
+    #[arg(long, value_enum, default_value_t = Parts::Both)]
+    pub part: Parts,
+}
+
+/// Which parts to run.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum Parts {
+    /// Only part 1.
+    #[value(name = "1")]
+    One,
+    /// Only part 2.
+    #[value(name = "2")]
+    Two,
+    /// Both of them.
+    #[default]
+    Both,
+}
+
+impl Parts {
+    /// Whether `part` should be run.
+    #[must_use]
+    pub fn wants(self, part: u32) -> bool {
+        match self {
+            Parts::One => part == 1,
+            Parts::Two => part == 2,
+            Parts::Both => true,
+        }
+    }
+}
+
+/// How answers get printed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum Format {
+    /// Each day's own human readable lines.
+    #[default]
+    Text,
+    /// One JSON object per answer per line. See [`Answer`] for the schema.
+    Json,
+}
+
+/// One part's answer.
+///
+/// As JSON this is always
+/// `{"day":N,"part":N,"answer":"...","elapsed_ns":N,"detail":null|"..."}`.
+/// The answer is always a string so a number and day25's SNAFU look alike.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Answer {
+    /// Day number.
+    pub day: u32,
+    /// Part number.
+    pub part: u32,
+    /// The answer.
+    pub answer: String,
+    /// Wall clock time to compute the answer, not counting parsing.
+    #[serde(rename = "elapsed_ns", serialize_with = "nanos")]
+    pub elapsed: Duration,
+    /// Anything else worth showing alongside the answer.
+    pub detail: Option<String>,
+}
+
+fn nanos<S: Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u128(d.as_nanos())
+}
+
+impl Answer {
+    /// Attach a detail.
+    #[must_use]
+    pub fn with_detail(self, detail: impl Display) -> Self {
+        Self {
+            detail: Some(detail.to_string()),
+            ..self
+        }
+    }
+
+    /// This answer as a single line of JSON.
+    ///
+    /// # Errors
+    /// If serializing fails.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+/// Run `f` and time it as `part` of `day`.
+///
+/// # Errors
+/// Whatever `f` returns.
+pub fn time<T: Display>(day: u32, part: u32, f: impl FnOnce() -> Result<T>) -> Result<Answer> {
+    let now = Instant::now();
+    let answer = f()?;
+    let elapsed = now.elapsed();
+    Ok(Answer {
+        day,
+        part,
+        answer: answer.to_string(),
+        elapsed,
+        detail: None,
+    })
+}
+
+/// Prints answers for one day in the format asked for.
+#[derive(Clone, Debug)]
+pub struct Report {
+    day: u32,
+    format: Format,
+    debug: bool,
+    parts: Parts,
+}
+
+impl Report {
+    /// Whether `part` was asked for. Days that time and print parts
+    /// themselves check this first.
+    #[must_use]
+    pub fn wants(&self, part: u32) -> bool {
+        self.parts.wants(part)
+    }
+
+    /// Time `f` as `part` and print it. In text mode that's `prefix`
+    /// followed by the answer which keeps each day's existing output.
+    ///
+    /// `f` isn't called at all if `part` wasn't asked for and this returns
+    /// `None`.
+    ///
+    /// # Errors
+    /// Whatever `f` returns.
+    pub fn part<T: Display>(
+        &self,
+        part: u32,
+        prefix: &str,
+        f: impl FnOnce() -> Result<T>,
+    ) -> Result<Option<Answer>> {
+        if !self.wants(part) {
+            return Ok(None);
+        }
+        let a = time(self.day, part, f)?;
+        self.print(prefix, &a)?;
+        Ok(Some(a))
+    }
+
+    /// Print an answer timed elsewhere, usually one with a detail attached.
+    ///
+    /// # Errors
+    /// If it can't be serialized.
+    pub fn print(&self, prefix: &str, a: &Answer) -> Result<()> {
+        match self.format {
+            Format::Text => {
+                print!("{prefix}{}", a.answer);
+                if let Some(d) = &a.detail {
+                    print!(" ({d})");
+                }
+                // Multi line answers (day10's picture) end with their own newline.
+                if !a.answer.ends_with('\n') {
+                    println!();
+                }
+                if self.debug {
+                    println!("part{} took {:?}", a.part, a.elapsed);
+                }
+            }
+            Format::Json => println!("{}", a.to_json()?),
+        }
+        Ok(())
+    }
+
+    /// Print something that isn't an answer, like a trace or a picture.
+    /// Alongside text answers that's stdout but with json it goes to stderr
+    /// so stdout stays one answer per line.
+    pub fn show(&self, text: impl Display) {
+        match self.format {
+            Format::Text => println!("{text}"),
+            Format::Json => eprintln!("{text}"),
+        }
+    }
+}
+
+/// Where an input comes from once resolved.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Source {
+    /// Read from stdin.
+    Stdin,
+    /// Read from the given file.
+    File(PathBuf),
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::File(p) => write!(f, "{}", p.display()),
+        }
+    }
+}
+
+/// An input read into memory along with where it came from.
+#[derive(Clone, Debug)]
+pub struct InputFile {
+    /// Where it came from.
+    pub source: Source,
+    /// Everything in it.
+    pub text: String,
+}
+
+impl InputFile {
+    /// Read all of `source`.
+    ///
+    /// # Errors
+    /// If it can't be read.
+    pub fn read(source: Source) -> Result<Self> {
+        let text = source.read_to_string()?;
+        Ok(Self { source, text })
+    }
+
+    /// Run a day's `parse` over the text. A [`ParseError`] coming back gets
+    /// this file's name attached.
+    ///
+    /// # Errors
+    /// Whatever `parse` returns.
+    pub fn parse<'a, T>(&'a self, parse: impl FnOnce(&'a str) -> Result<T>) -> Result<T> {
+        parse(&self.text).map_err(|e| self.source.locate(e))
+    }
+}
+
+impl Args {
+    /// A [`Report`] for printing `day`'s answers.
+    #[must_use]
+    pub fn report(&self, day: u32) -> Report {
+        Report {
+            day,
+            format: self.format,
+            debug: self.debug,
+            parts: self.part,
+        }
+    }
+
+    /// Resolve `filename` for the day whose crate lives at `manifest_dir`.
+    /// Callers pass `env!("CARGO_MANIFEST_DIR")`.
+    #[must_use]
+    pub fn source(&self, manifest_dir: &str) -> Source {
+        resolve(
+            Path::new(manifest_dir),
+            &self.filename,
+            env::var_os(INPUT_DIR_ENV).map(PathBuf::from).as_deref(),
+        )
+    }
+
+    /// Read the input for the day at `manifest_dir`, keeping track of where
+    /// it came from for error messages.
+    ///
+    /// # Errors
+    /// If the input can't be read.
+    pub fn input(&self, manifest_dir: &str) -> Result<InputFile> {
+        InputFile::read(self.source(manifest_dir))
+    }
+}
+
+impl Source {
+    /// Read everything from this source.
+    ///
+    /// # Errors
+    /// If the file can't be opened or stdin can't be read.
+    pub fn read_to_string(&self) -> Result<String> {
+        match self {
+            Source::Stdin => {
+                let mut s = String::new();
+                io::stdin()
+                    .read_to_string(&mut s)
+                    .wrap_err("reading stdin")?;
+                Ok(s)
+            }
+            Source::File(p) => {
+                fs::read_to_string(p).wrap_err_with(|| format!("reading {}", p.display()))
+            }
+        }
+    }
+
+    /// Attach this source's name to `err` if it's a [`ParseError`].
+    /// Anything else is passed through untouched.
+    pub fn locate(&self, err: color_eyre::Report) -> color_eyre::Report {
+        match err.downcast::<ParseError>() {
+            Ok(e) => e.in_file(self).into(),
+            Err(err) => err,
+        }
+    }
+}
+
+/// Resolve `filename` for the day whose crate lives at `manifest_dir`.
+///
+/// `-` is stdin and absolute paths are used as is. Relative paths land in
+/// `input_dir/<day>` if an input directory is given, otherwise in the crate
+/// directory itself. `<day>` is the last component of `manifest_dir`.
+#[must_use]
+pub fn resolve(manifest_dir: &Path, filename: &str, input_dir: Option<&Path>) -> Source {
+    if filename == STDIN {
+        return Source::Stdin;
+    }
+    let path = Path::new(filename);
+    if path.is_absolute() {
+        return Source::File(path.to_path_buf());
+    }
+    let base = match (input_dir, manifest_dir.file_name()) {
+        (Some(dir), Some(day)) => dir.join(day),
+        _ => manifest_dir.to_path_buf(),
+    };
+    Source::File(base.join(path))
+}
+
+/// Input for benchmarking one day.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BenchInput {
+    /// The input itself.
+    pub text: String,
+    /// True when this is the published example rather than a puzzle input.
+    /// Days whose parts are tuned to puzzle sized input need to know.
+    pub example: bool,
+}
+
+/// Input to benchmark the day whose crate lives at `manifest_dir`.
+///
+/// That's the puzzle input (`input.txt`, resolved like the binaries do) when
+/// there is one since the slow days are only slow on those. Otherwise it
+/// falls back to the day's `example.txt` so benches run on a fresh checkout.
+///
+/// # Errors
+/// If neither can be read.
+pub fn bench_input(manifest_dir: &str) -> Result<BenchInput> {
+    let input_dir = env::var_os(INPUT_DIR_ENV).map(PathBuf::from);
+    if let Source::File(p) = resolve(Path::new(manifest_dir), "input.txt", input_dir.as_deref()) {
+        if p.exists() {
+            return Ok(BenchInput {
+                text: Source::File(p).read_to_string()?,
+                example: false,
+            });
+        }
+    }
+    Ok(BenchInput {
+        text: Source::File(Path::new(manifest_dir).join("example.txt")).read_to_string()?,
+        example: true,
+    })
+}
+
+/// Criterion benchmarks for a day, as the whole of its `benches/dayN.rs`.
+/// Parsing and each part are timed separately so a regression shows up
+/// against the step that caused it. Uses [`bench_input`] for the input and
+/// expects `criterion` as a dev-dependency.
+///
+/// A part followed by `(example: f)` benches `f` instead when running on
+/// the example, for parts that only make sense on a puzzle input. A
+/// trailing `sample_size = n` cuts the samples taken for the slow days.
+///
+/// ```text
+/// aoc_common::benches!(day10, [part1, part2 (example: day10::picture)]);
+/// aoc_common::benches!(day16, [part1, part2], sample_size = 10);
+/// ```
+#[macro_export]
+macro_rules! benches {
+    (@part $g:ident, $parsed:ident, $day:ident::$part:ident) => {
+        $g.bench_function(stringify!($part), |b| {
+            b.iter(|| $day::$part(::std::hint::black_box(&$parsed)));
+        });
+    };
+    (@part $g:ident, $parsed:ident, $day:ident::$part:ident, $input:ident, $example:expr) => {
+        if $input.example {
+            let example = $example;
+            $g.bench_function(stringify!($part), |b| {
+                b.iter(|| example(::std::hint::black_box(&$parsed)));
+            });
+        } else {
+            $crate::benches!(@part $g, $parsed, $day::$part);
+        }
+    };
+    ($day:ident, [$($part:ident $((example: $example:expr))?),+ $(,)?] $(, sample_size = $n:literal)?) => {
+        fn main() {
+            let input = $crate::bench_input(env!("CARGO_MANIFEST_DIR")).unwrap();
+            let parsed = $day::parse(&input.text).unwrap();
+
+            let mut c = ::criterion::Criterion::default().configure_from_args();
+            let mut g = c.benchmark_group(stringify!($day));
+            $(g.sample_size($n);)?
+            g.bench_function("parse", |b| {
+                b.iter(|| $day::parse(::std::hint::black_box(&input.text)));
+            });
+            $($crate::benches!(@part g, parsed, $day::$part $(, input, $example)?);)+
+            g.finish();
+            c.final_summary();
+        }
+    };
+}
+
+/// Every line of input with its index as counted by `enumerate`, which is
+/// what a [`ParseError`] takes to point at it.
+pub fn lines(input: &str) -> std::iter::Enumerate<std::str::Lines<'_>> {
+    input.lines().enumerate()
+}
+
+/// Split input into blocks of lines separated by one or more blank (or
+/// all whitespace) lines. Each line keeps its index as given by [`lines`]
+/// so a [`ParseError`] can still point at it.
+#[must_use]
+pub fn blocks(input: &str) -> Vec<Vec<(usize, &str)>> {
+    let mut blocks = Vec::new();
+    let mut cur = Vec::new();
+    for (line_num, line) in lines(input) {
+        if line.trim().is_empty() {
+            if !cur.is_empty() {
+                blocks.push(cur);
+                cur = Vec::new();
+            }
+            continue;
+        }
+        cur.push((line_num, line));
+    }
+    if !cur.is_empty() {
+        blocks.push(cur);
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_input() {
+        let day = Path::new("/src/advent/day5");
+        assert_eq!(resolve(day, "-", None), Source::Stdin);
+        assert_eq!(resolve(day, "-", Some(Path::new("/inputs"))), Source::Stdin);
+        assert_eq!(
+            resolve(day, "input.txt", None),
+            Source::File(PathBuf::from("/src/advent/day5/input.txt"))
+        );
+        assert_eq!(
+            resolve(day, "more/test.txt", None),
+            Source::File(PathBuf::from("/src/advent/day5/more/test.txt"))
+        );
+        assert_eq!(
+            resolve(day, "input.txt", Some(Path::new("/inputs"))),
+            Source::File(PathBuf::from("/inputs/day5/input.txt"))
+        );
+        for dir in [None, Some(Path::new("/inputs"))] {
+            assert_eq!(
+                resolve(day, "/tmp/mine.txt", dir),
+                Source::File(PathBuf::from("/tmp/mine.txt"))
+            );
+        }
+    }
+
+    #[test]
+    fn numbered_lines() {
+        assert_eq!(
+            lines("a\n\nb\n").collect::<Vec<_>>(),
+            vec![(0, "a"), (1, ""), (2, "b")]
+        );
+        assert_eq!(lines("").count(), 0);
+    }
+
+    #[test]
+    fn blank_line_blocks() {
+        assert_eq!(
+            blocks("\n1\n2\n\n  \n\n3\n\n"),
+            vec![vec![(1, "1"), (2, "2")], vec![(6, "3")]]
+        );
+        assert!(blocks("").is_empty());
+        assert!(blocks("\n\n").is_empty());
+    }
+}
@@ -0,0 +1,52 @@
+//! Workspace wide benchmarks. Each day end to end (parse plus every part)
+//! and then every day back to back, which is what `aoc` with no days does.
+//!
+//! Only days with a puzzle input take part. The examples aren't all
+//! solvable with the puzzle's parameters (day15) and timing them wouldn't
+//! say much about the days that are slow anyway.
+// criterion_group! generates an undocumented pub fn.
+#![allow(missing_docs)]
+use aoc::{Day, Run, DAYS};
+use aoc_common::Parts;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+fn bench(c: &mut Criterion) {
+    let days: Vec<(&Day, String)> = DAYS
+        .iter()
+        .filter_map(|d| {
+            let input = aoc_common::bench_input(d.dir).unwrap();
+            (!input.example).then_some((d, input.text))
+        })
+        .collect();
+    if days.is_empty() {
+        eprintln!("no puzzle inputs found, nothing to benchmark");
+        return;
+    }
+
+    let mut g = c.benchmark_group("aoc");
+    // Some days take seconds so keep the minimum number of samples.
+    g.sample_size(10);
+    for (d, input) in &days {
+        g.bench_function(format!("day{}", d.day), |b| {
+            b.iter(|| {
+                d.run(black_box(input), Parts::Both)
+                    .and_then(Run::answers)
+                    .unwrap()
+            });
+        });
+    }
+    g.bench_function("all", |b| {
+        b.iter(|| {
+            for (d, input) in &days {
+                d.run(black_box(input), Parts::Both)
+                    .and_then(Run::answers)
+                    .unwrap();
+            }
+        });
+    });
+    g.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
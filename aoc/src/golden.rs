@@ -0,0 +1,108 @@
+//! Golden answers for regression checking.
+//!
+//! Next to each day's input lives an [`ANSWERS`] file holding the accepted
+//! answers as a JSON object, e.g. `{"part1": "24000", "part2": "45000"}`.
+//! Parts missing from the file aren't checked so a day can be filled in one
+//! part at a time.
+use crate::Day;
+use aoc_common::{Parts, Source};
+use color_eyre::eyre::{Result, WrapErr};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Name of the expected answers file kept next to each input.
+pub const ANSWERS: &str = "answers.json";
+
+/// An answer that doesn't match the golden one.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mismatch {
+    /// Day number.
+    pub day: u32,
+    /// Part number.
+    pub part: u32,
+    /// The golden answer.
+    pub expected: String,
+    /// What the day computes now.
+    pub actual: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "day{} part{}:", self.day, self.part)?;
+        // Line by line so multi line answers point at the rows that changed.
+        let expected = self.expected.lines().collect::<Vec<_>>();
+        let actual = self.actual.lines().collect::<Vec<_>>();
+        for i in 0..expected.len().max(actual.len()) {
+            match (expected.get(i), actual.get(i)) {
+                (Some(e), Some(a)) if e == a => writeln!(f, "    {e}")?,
+                (e, a) => {
+                    if let Some(e) = e {
+                        writeln!(f, "  - {e}")?;
+                    }
+                    if let Some(a) = a {
+                        writeln!(f, "  + {a}")?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read the golden answers in `source` keyed by part number.
+/// Returns `None` if there's no answers file.
+///
+/// # Errors
+/// If the file exists but can't be read, isn't a JSON object of strings or
+/// has a key other than `part1` or `part2`.
+pub fn expected(source: &Source) -> Result<Option<HashMap<u32, String>>> {
+    if let Source::File(p) = source {
+        if !p.exists() {
+            return Ok(None);
+        }
+    }
+    let raw: HashMap<String, String> = serde_json::from_str(&source.read_to_string()?)
+        .wrap_err_with(|| format!("parsing {source}"))?;
+    let mut answers = HashMap::new();
+    for (k, v) in raw {
+        let part = k
+            .strip_prefix("part")
+            .and_then(|p| p.parse::<u32>().ok())
+            .filter(|p| (1..=2).contains(p))
+            .ok_or_else(|| color_eyre::eyre::eyre!("{source}: bad key {k}"))?;
+        answers.insert(part, v);
+    }
+    Ok(Some(answers))
+}
+
+/// Run `day` against its input named `filename` and compare every part
+/// that has a golden answer in the [`ANSWERS`] file next to it. Returns `None` if the input or the answers
+/// file is missing so there's nothing to check.
+///
+/// # Errors
+/// If the day fails to run or the answers can't be read.
+pub fn check(day: &Day, filename: &str) -> Result<Option<Vec<Mismatch>>> {
+    let answers = match day.source(filename) {
+        Source::File(p) if !p.exists() => return Ok(None),
+        Source::File(p) => Source::File(p.with_file_name(ANSWERS)),
+        Source::Stdin => day.source(ANSWERS),
+    };
+    let Some(expected) = expected(&answers)? else {
+        return Ok(None);
+    };
+    let answers = day.run_file(filename, Parts::Both)?.answers()?;
+    let mut mismatches = Vec::new();
+    for a in answers {
+        if let Some(e) = expected.get(&a.part) {
+            if *e != a.answer {
+                mismatches.push(Mismatch {
+                    day: day.day,
+                    part: a.part,
+                    expected: e.clone(),
+                    actual: a.answer,
+                });
+            }
+        }
+    }
+    Ok(Some(mismatches))
+}
@@ -0,0 +1,187 @@
+//! Runs every day's solver in process and times each part.
+//!
+//! Each day crate exposes `parse`, `part1` and `part2` so the runner just
+//! needs a table of them. Answers are turned into strings here so the days
+//! can keep returning whatever type is natural for them.
+use aoc_common::{Answer, InputFile, Parts, Source};
+use color_eyre::eyre::{eyre, Result};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+pub mod golden;
+
+/// First day of the calendar.
+pub const FIRST: u32 = 1;
+
+/// Last day of the calendar.
+pub const LAST: u32 = 25;
+
+/// One day's solver.
+pub struct Day {
+    /// Day number.
+    pub day: u32,
+    /// The day's crate directory. Inputs are resolved relative to this the
+    /// same way the day's own binary does.
+    pub dir: &'static str,
+    run: fn(&str, Parts) -> Result<Run>,
+}
+
+/// Everything from running one day.
+#[derive(Clone, Debug)]
+pub struct Run {
+    /// Day number.
+    pub day: u32,
+    /// Wall clock time to parse the input.
+    pub parse: Duration,
+    /// Each part that was run in order. Day 25 only has one. A part failing
+    /// doesn't stop the ones after it.
+    pub parts: Vec<PartRun>,
+}
+
+/// One part of a [`Run`].
+#[derive(Clone, Debug)]
+pub struct PartRun {
+    /// Part number.
+    pub part: u32,
+    /// The answer or, if the part failed, its error with every cause.
+    pub result: std::result::Result<Answer, String>,
+}
+
+impl Run {
+    /// The answer from every part.
+    ///
+    /// # Errors
+    /// The first part that failed.
+    pub fn answers(self) -> Result<Vec<Answer>> {
+        self.parts
+            .into_iter()
+            .map(|p| {
+                p.result
+                    .map_err(|e| eyre!("day{} part{} - {e}", self.day, p.part))
+            })
+            .collect()
+    }
+}
+
+impl Day {
+    /// Parse `input` and run the parts asked for.
+    ///
+    /// # Errors
+    /// Whatever the day's parse returns. Parts that fail are kept in the
+    /// [`Run`].
+    pub fn run(&self, input: &str, parts: Parts) -> Result<Run> {
+        (self.run)(input, parts)
+    }
+
+    /// Where this day's input named `filename` comes from. Anything else
+    /// kept next to the input (like [`golden::ANSWERS`]) resolves the same way.
+    #[must_use]
+    pub fn source(&self, filename: &str) -> Source {
+        aoc_common::resolve(
+            Path::new(self.dir),
+            filename,
+            env::var_os(aoc_common::INPUT_DIR_ENV)
+                .map(PathBuf::from)
+                .as_deref(),
+        )
+    }
+
+    /// Read this day's input named `filename` and run the parts asked for.
+    ///
+    /// # Errors
+    /// If the input can't be read, otherwise as for [`Day::run`].
+    pub fn run_file(&self, filename: &str, parts: Parts) -> Result<Run> {
+        let file = InputFile::read(self.source(filename))?;
+        file.parse(|text| self.run(text, parts))
+    }
+}
+
+macro_rules! day {
+    ($day:literal, $krate:ident) => {
+        day!($day, $krate, [part1, part2])
+    };
+    ($day:literal, $krate:ident, [$($part:ident),+]) => {
+        Day {
+            day: $day,
+            dir: concat!(env!("CARGO_MANIFEST_DIR"), "/../", stringify!($krate)),
+            run: |input, wanted| {
+                let now = Instant::now();
+                let parsed = $krate::parse(input)?;
+                let parse = now.elapsed();
+                let mut parts = Vec::new();
+                let mut part = 0;
+                $(
+                    part += 1;
+                    if wanted.wants(part) {
+                        parts.push(PartRun {
+                            part,
+                            result: aoc_common::time($day, part, || $krate::$part(&parsed))
+                                .map_err(|e| format!("{e:#}")),
+                        });
+                    }
+                )+
+                Ok(Run {
+                    day: $day,
+                    parse,
+                    parts,
+                })
+            },
+        }
+    };
+}
+
+/// Every day in order.
+pub static DAYS: [Day; 25] = [
+    day!(1, day1),
+    day!(2, day2),
+    day!(3, day3),
+    day!(4, day4),
+    day!(5, day5),
+    day!(6, day6),
+    day!(7, day7),
+    day!(8, day8),
+    day!(9, day9),
+    day!(10, day10),
+    day!(11, day11),
+    day!(12, day12),
+    day!(13, day13),
+    day!(14, day14),
+    day!(15, day15),
+    day!(16, day16),
+    day!(17, day17),
+    day!(18, day18),
+    day!(19, day19),
+    day!(20, day20),
+    day!(21, day21),
+    day!(22, day22),
+    day!(23, day23),
+    day!(24, day24),
+    day!(25, day25, [part1]),
+];
+
+/// Look up a day by number.
+#[must_use]
+pub fn day(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_answers_before_a_failure() -> Result<()> {
+        // day10's example has no letters for part 2 to read.
+        let example = concat!(env!("CARGO_MANIFEST_DIR"), "/../day10/example.txt");
+        let run = day(10).unwrap().run_file(example, Parts::Both)?;
+        assert_eq!(run.parts.len(), 2);
+        assert_eq!(
+            run.parts[0].result.as_ref().map(|a| a.answer.as_str()),
+            Ok("13140")
+        );
+        assert!(run.parts[1].result.is_err());
+        assert!(run.answers().is_err());
+        Ok(())
+    }
+}
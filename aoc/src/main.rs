@@ -0,0 +1,178 @@
+//! Runs some or all of the advent 2022 days and reports answers and times.
+use aoc::{Run, DAYS, FIRST, LAST};
+use aoc_common::Format;
+use clap::Parser;
+use color_eyre::eyre::{eyre, Result};
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+#[derive(Parser)]
+#[command(author, version, about)]
+struct Args {
+    #[command(flatten)]
+    common: aoc_common::Args,
+
+    /// Days to run. A single day (`7`), a range (`3-9`) or all of them if
+    /// omitted.
+    #[arg(value_parser = parse_days)]
+    days: Option<RangeInclusive<u32>>,
+}
+
+fn parse_days(s: &str) -> Result<RangeInclusive<u32>, String> {
+    let (lo, hi) = s.split_once('-').unwrap_or((s, s));
+    let lo = lo.trim().parse::<u32>().map_err(|e| format!("{lo}: {e}"))?;
+    let hi = hi.trim().parse::<u32>().map_err(|e| format!("{hi}: {e}"))?;
+    if lo < FIRST || hi > LAST || lo > hi {
+        return Err(format!("{s} isn't within {FIRST}-{LAST}"));
+    }
+    Ok(lo..=hi)
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+    let args: Args = Args::parse();
+
+    let days = args.days.unwrap_or(FIRST..=LAST);
+    let mut rows = Vec::new();
+    let mut failed = 0;
+    for d in DAYS.iter().filter(|d| days.contains(&d.day)) {
+        if args.common.debug {
+            eprintln!("day{} - {:?}", d.day, d.source(&args.common.filename));
+        }
+        let run = d.run_file(&args.common.filename, args.common.part);
+        // A day with any part failing counts once.
+        if run
+            .as_ref()
+            .map_or(true, |r| r.parts.iter().any(|p| p.result.is_err()))
+        {
+            failed += 1;
+        }
+        match (run, args.common.format) {
+            (Ok(run), Format::Text) => rows.push(Row::from(&run)),
+            // Keep stdout parseable, failures only go to stderr.
+            (Ok(run), Format::Json) => {
+                for p in &run.parts {
+                    match &p.result {
+                        Ok(a) => println!("{}", a.to_json()?),
+                        Err(e) => eprintln!("day{} part{} - {e}", d.day, p.part),
+                    }
+                }
+            }
+            (Err(e), Format::Text) => rows.push(Row::failed(d.day, &format!("{e:#}"))),
+            (Err(e), Format::Json) => eprintln!("day{} - {e:#}", d.day),
+        }
+    }
+    if args.common.format == Format::Text {
+        print_table(&rows);
+    }
+    if failed > 0 {
+        return Err(eyre!("{failed} day(s) failed"));
+    }
+    Ok(())
+}
+
+// One line of the table, already formatted.
+struct Row {
+    cells: Vec<String>,
+    // Answers too tall for the table and multi line errors get printed
+    // below it.
+    extra: Vec<(String, String)>,
+}
+
+const HEADER: [&str; 6] = ["Day", "Parse", "Part 1", "Time", "Part 2", "Time"];
+
+fn format_duration(d: Duration) -> String {
+    format!("{d:.2?}")
+}
+
+impl Row {
+    fn from(run: &Run) -> Self {
+        let mut cells = vec![run.day.to_string(), format_duration(run.parse)];
+        let mut extra = Vec::new();
+        // Keep each answer in its own column when only one part was run.
+        for part in 1..=2 {
+            let Some(p) = run.parts.iter().find(|p| p.part == part) else {
+                cells.extend([String::from("-"), String::from("-")]);
+                continue;
+            };
+            let name = format!("day{} part{part}", run.day);
+            match &p.result {
+                Ok(a) => {
+                    if a.answer.contains('\n') {
+                        cells.push(String::from("(below)"));
+                        extra.push((name, a.answer.clone()));
+                    } else {
+                        cells.push(a.answer.clone());
+                    }
+                    cells.push(format_duration(a.elapsed));
+                }
+                Err(e) => {
+                    cells.push(error_cell(&mut extra, &format!("{name} error"), e));
+                    cells.push(String::from("-"));
+                }
+            }
+        }
+        while cells.len() < HEADER.len() {
+            cells.push(String::from("-"));
+        }
+        Row { cells, extra }
+    }
+
+    fn failed(day: u32, err: &str) -> Self {
+        let mut extra = Vec::new();
+        let cell = error_cell(&mut extra, &format!("day{day} error"), err);
+        let mut cells = vec![day.to_string(), String::from("-"), cell];
+        while cells.len() < HEADER.len() {
+            cells.push(String::from("-"));
+        }
+        Row { cells, extra }
+    }
+}
+
+// The cell for an error, moving it into `extra` under `name` if it's too
+// tall for the table.
+fn error_cell(extra: &mut Vec<(String, String)>, name: &str, err: &str) -> String {
+    // Parse errors point into the input over several lines.
+    if err.contains('\n') {
+        extra.push((String::from(name), format!("{err}\n")));
+        String::from("error (below)")
+    } else {
+        format!("error: {err}")
+    }
+}
+
+fn print_table(rows: &[Row]) {
+    let mut widths = HEADER.map(str::len);
+    for r in rows {
+        for (w, c) in widths.iter_mut().zip(&r.cells) {
+            *w = (*w).max(c.len());
+        }
+    }
+    let line = |cells: &mut dyn Iterator<Item = &str>| {
+        let out = cells
+            .zip(widths)
+            .map(|(c, w)| format!("{c:<w$}"))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        println!("{}", out.trim_end());
+    };
+    line(&mut HEADER.iter().copied());
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<_>>()
+            .join("-+-")
+    );
+    for r in rows {
+        line(&mut r.cells.iter().map(String::as_str));
+    }
+    for r in rows {
+        for (name, answer) in &r.extra {
+            println!();
+            println!("{name}:");
+            print!("{answer}");
+        }
+    }
+}
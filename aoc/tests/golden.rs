@@ -0,0 +1,87 @@
+//! Checks every day against the golden answers next to its input.
+//!
+//! Inputs aren't checked in so days without an input or answers file are
+//! skipped. Point `AOC_INPUT_DIR` at a directory of `dayN/input.txt` and
+//! `dayN/answers.json` to check them from elsewhere.
+//!
+//! The checker itself is tested against the fixtures in `tests/fixtures`.
+use aoc::golden::{self, Mismatch, ANSWERS};
+use aoc::DAYS;
+use aoc_common::Source;
+use color_eyre::eyre::Result;
+use std::path::PathBuf;
+
+fn fixture(path: &str) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "tests", "fixtures", path]
+        .iter()
+        .collect()
+}
+
+#[test]
+fn golden_answers() {
+    let mut failures = Vec::new();
+    let mut skipped = Vec::new();
+    for d in &DAYS {
+        match golden::check(d, "input.txt") {
+            Ok(Some(mismatches)) => failures.extend(mismatches.iter().map(ToString::to_string)),
+            Ok(None) => skipped.push(d.day.to_string()),
+            Err(e) => failures.push(format!("day{}: {e:#}\n", d.day)),
+        }
+    }
+    if !skipped.is_empty() {
+        println!("no input or {ANSWERS} for days {}", skipped.join(", "));
+    }
+    assert!(
+        failures.is_empty(),
+        "answers changed (- golden, + now):\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn reports_mismatch() -> Result<()> {
+    // day1's example with part 2 deliberately off by one.
+    let input = fixture("day1/example.txt");
+    let mismatches = golden::check(&DAYS[0], &input.to_string_lossy())?;
+    let expected = Mismatch {
+        day: 1,
+        part: 2,
+        expected: String::from("45001"),
+        actual: String::from("45000"),
+    };
+    assert_eq!(mismatches, Some(vec![expected.clone()]));
+    assert_eq!(expected.to_string(), "day1 part2:\n  - 45001\n  + 45000\n");
+    assert_eq!(
+        golden::check(&DAYS[0], &fixture("day1/missing.txt").to_string_lossy())?,
+        None
+    );
+    Ok(())
+}
+
+#[test]
+fn multi_line_diff() {
+    let m = Mismatch {
+        day: 10,
+        part: 2,
+        expected: String::from("#..#\n.##.\n#..#\n"),
+        actual: String::from("#..#\n.#..\n#..#\n##..\n"),
+    };
+    assert_eq!(
+        m.to_string(),
+        "day10 part2:\n    #..#\n  - .##.\n  + .#..\n    #..#\n  + ##..\n"
+    );
+}
+
+#[test]
+fn bad_key() -> Result<()> {
+    let answers = Source::File(fixture("bad_key/answers.json"));
+    let err = golden::expected(&answers).unwrap_err();
+    assert!(err.to_string().ends_with("bad key partx"), "{err}");
+    assert_eq!(
+        golden::expected(&Source::File(fixture("answers.json")))?,
+        None
+    );
+    let answers = golden::expected(&Source::File(fixture("day1/answers.json")))?;
+    assert_eq!(answers.map(|a| a.len()), Some(2));
+    Ok(())
+}
@@ -0,0 +1,2 @@
+//! day1 benchmarks.
+aoc_common::benches!(day1, [part1, part2]);
@@ -0,0 +1,80 @@
+//! day1 advent 2022
+use aoc_common::{blocks, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+
+/// Total calories carried by each elf, sorted smallest to largest.
+pub type Input = Vec<u64>;
+
+/// Parse the calorie list into per elf totals.
+///
+/// # Errors
+/// If a line isn't blank or a single number.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut elves = Vec::new();
+    for elf in blocks(input) {
+        let mut total = 0;
+        for (line_num, line) in elf {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() > 1 {
+                return Err(ParseError::new(line_num, line, fields[1], "end of line").into());
+            }
+            total += ParseError::parse::<u64>(line_num, line, fields[0], "a calorie count")?;
+        }
+        elves.push(total);
+    }
+    elves.sort_unstable();
+    Ok(elves)
+}
+
+/// The most calories any one elf carries.
+///
+/// # Errors
+/// [`NoAnswer`] if there are no elves.
+pub fn part1(elves: &Input) -> Result<u64> {
+    match elves.last() {
+        Some(e) => Ok(*e),
+        None => Err(NoAnswer::new("there are no elves").into()),
+    }
+}
+
+/// The calories carried by the top 3 elves combined.
+///
+/// # Errors
+/// [`NoAnswer`] with fewer than 3 elves.
+pub fn part2(elves: &Input) -> Result<u64> {
+    if elves.len() < 3 {
+        return Err(NoAnswer::new(format!("need 3 elves, only have {}", elves.len())).into());
+    }
+    Ok(elves.iter().rev().take(3).sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 24000);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 45000);
+        Ok(())
+    }
+
+    #[test]
+    fn too_few_elves() -> Result<()> {
+        let elves = parse("1000\n\n2000\n")?;
+        assert_eq!(part1(&elves)?, 2000);
+        assert!(part2(&elves).unwrap_err().is::<NoAnswer>());
+        assert!(part1(&parse("")?).unwrap_err().is::<NoAnswer>());
+        let gaps = parse("\n1000\n\n\n2000\n3000\n")?;
+        assert_eq!(part1(&gaps)?, 5000);
+        assert!(part2(&gaps).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+//! day10 benchmarks.
+// The example draws stripes, not letters, so only the picture works.
+aoc_common::benches!(day10, [part1, part2 (example: day10::picture)]);
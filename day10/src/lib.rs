@@ -0,0 +1,696 @@
+//! day10 advent 2022
+use aoc_common::{lines, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use std::fmt;
+
+/// One instruction of the program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    /// Do nothing for 1 cycle.
+    Noop,
+    /// Add the value to X after 2 cycles.
+    Addx(i32),
+}
+
+/// The program.
+pub type Input = Vec<Instruction>;
+
+/// Parse the program.
+///
+/// # Errors
+/// On an unknown instruction or an `addx` without a number.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut prog = Vec::new();
+    for (line_num, line) in lines(input) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        let operands = match parts.first().copied().unwrap_or_default() {
+            "noop" => {
+                prog.push(Instruction::Noop);
+                1
+            }
+            "addx" => {
+                let val = parts.get(1).copied().unwrap_or_default();
+                prog.push(Instruction::Addx(ParseError::parse(
+                    line_num, line, val, "a number",
+                )?));
+                2
+            }
+            p => {
+                return Err(ParseError::new(line_num, line, p, "`noop` or `addx`").into());
+            }
+        };
+        if let Some(extra) = parts.get(operands) {
+            return Err(ParseError::new(line_num, line, extra, "end of line").into());
+        }
+    }
+    Ok(prog)
+}
+
+/// Sum of the signal strengths at cycles 20, 60, 100... 220.
+///
+/// # Errors
+/// [`NoAnswer`] if the program stops before the last sample at cycle 220.
+pub fn part1(prog: &Input) -> Result<i64> {
+    signal_strength(prog, Samples::PUZZLE)
+}
+
+/// The letters the program draws on the CRT.
+///
+/// # Errors
+/// [`NoAnswer`] if the program stops before the screen is drawn or the
+/// picture isn't letters [`ocr`] can read.
+pub fn part2(prog: &Input) -> Result<String> {
+    ocr(&picture(prog)?)
+}
+
+/// The picture the program draws on the puzzle's 40x6 CRT, one line per
+/// row.
+///
+/// # Errors
+/// [`NoAnswer`] if the program stops before the screen is drawn.
+pub fn picture(prog: &Input) -> Result<String> {
+    draw(prog, Screen::PUZZLE)
+}
+
+/// Sum of the signal strengths (cycle times X) at the `samples` cycles.
+///
+/// # Errors
+/// [`NoAnswer`] if the program stops before the last sample.
+pub fn signal_strength(prog: &[Instruction], samples: Samples) -> Result<i64> {
+    let mut signal = Signal::new(samples);
+    let mut cpu = Cpu::new(prog);
+    cpu.run(&mut [&mut signal]);
+    enough(cpu.cycle(), samples.last)?;
+    Ok(signal.sum())
+}
+
+/// The picture the program draws on a CRT of size `screen`.
+///
+/// # Errors
+/// [`NoAnswer`] if the program stops before the screen is drawn.
+pub fn draw(prog: &[Instruction], screen: Screen) -> Result<String> {
+    let mut crt = Crt::new(screen);
+    let mut cpu = Cpu::new(prog);
+    cpu.run(&mut [&mut crt]);
+    enough(cpu.cycle(), screen.cycles())?;
+    Ok(crt.picture().to_string())
+}
+
+// Each letter is 4 pixels wide, 6 high and followed by a blank column.
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+
+// The capital letters the puzzles draw, row by row.
+const FONT: [(char, [&str; GLYPH_HEIGHT]); 16] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Read the capital letters drawn in `picture` (as returned by
+/// [`picture`]). Blank spaces between letters read as spaces and ones at
+/// the end are dropped.
+///
+/// # Errors
+/// [`NoAnswer`] if the picture isn't 6 rows or has something in it that
+/// isn't one of the letters the puzzles use.
+pub fn ocr(picture: &str) -> Result<String> {
+    let rows = picture.lines().collect::<Vec<_>>();
+    if rows.len() != GLYPH_HEIGHT {
+        return Err(NoAnswer::new(format!(
+            "the picture is {} rows high, letters are {GLYPH_HEIGHT}",
+            rows.len()
+        ))
+        .into());
+    }
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let mut text = String::new();
+    for col in (0..width).step_by(GLYPH_WIDTH + 1) {
+        let glyph = rows
+            .iter()
+            .map(|r| r.get(col..col + GLYPH_WIDTH).unwrap_or_default())
+            .collect::<Vec<_>>();
+        if glyph.iter().all(|r| !r.contains('#')) {
+            text.push(' ');
+            continue;
+        }
+        let Some((c, _)) = FONT.iter().find(|(_, g)| *g == glyph[..]) else {
+            return Err(NoAnswer::new(format!(
+                "can't read the letter in columns {} to {}",
+                col + 1,
+                col + GLYPH_WIDTH
+            ))
+            .into());
+        };
+        text.push(*c);
+    }
+    Ok(text.trim_end().to_string())
+}
+
+/// The picture `text` makes in the puzzle's font on a `screen` sized CRT,
+/// starting at the top left, laid out like [`picture`]. Spaces are left
+/// blank.
+///
+/// # Errors
+/// [`NoAnswer`] if a letter isn't in the font or the text doesn't fit.
+pub fn render(text: &str, screen: Screen) -> Result<String> {
+    let width = usize::try_from(screen.width)?;
+    let height = usize::try_from(screen.height)?;
+    let needed = (text.chars().count() * (GLYPH_WIDTH + 1)).saturating_sub(1);
+    if needed > width || (!text.is_empty() && height < GLYPH_HEIGHT) {
+        return Err(NoAnswer::new(format!(
+            "{text:?} needs a {needed}x{GLYPH_HEIGHT} screen, it's {width}x{height}"
+        ))
+        .into());
+    }
+    let mut rows = vec![vec!['.'; width]; height];
+    for (i, c) in text.chars().enumerate() {
+        if c == ' ' {
+            continue;
+        }
+        let Some((_, glyph)) = FONT.iter().find(|(f, _)| *f == c) else {
+            return Err(NoAnswer::new(format!("there's no letter {c:?} in the font")).into());
+        };
+        for (row, line) in rows.iter_mut().zip(glyph) {
+            for (j, p) in line.chars().enumerate() {
+                row[i * (GLYPH_WIDTH + 1) + j] = p;
+            }
+        }
+    }
+    Ok(rows
+        .into_iter()
+        .map(|r| r.into_iter().chain(['\n']).collect::<String>())
+        .collect())
+}
+
+/// A program that draws `text` on a `screen` sized CRT, the way
+/// [`render`] lays it out.
+///
+/// # Errors
+/// [`NoAnswer`] if [`render`] can't lay out the text or no program can draw
+/// it. X is 1 for at least the first two cycles, lighting the first two
+/// pixels, so the text has to start with a letter like `E` whose top row
+/// starts `##`.
+pub fn assemble(text: &str, screen: Screen) -> Result<Input> {
+    let lit = render(text, screen)?
+        .chars()
+        .filter(|c| *c != '\n')
+        .map(|c| c == '#')
+        .collect::<Vec<_>>();
+    let cycles = lit.len();
+    let width = i32::try_from(screen.width)?;
+    // X far enough left or right is as good as any further out.
+    let xs = (-2..=width + 1).collect::<Vec<_>>();
+    let cols = (0..width).cycle().take(cycles).collect::<Vec<_>>();
+    // Whether X at index `xi` of `xs` draws cycle `i` right.
+    let draws = |i: usize, xi: usize| i >= cycles || ((cols[i] - xs[xi]).abs() <= 1) == lit[i];
+
+    // can[i][xi]: an instruction starting on cycle i with that X can go on
+    // to draw the rest of the screen. Past the end anything goes.
+    let mut can = vec![vec![true; xs.len()]; cycles + 2];
+    for i in (0..cycles).rev() {
+        let (now, later) = can.split_at_mut(i + 1);
+        let any_after = later[1].contains(&true);
+        for (xi, c) in now[i].iter_mut().enumerate() {
+            *c = draws(i, xi) && (later[0][xi] || (draws(i + 1, xi) && any_after));
+        }
+    }
+
+    let mut xi = xs.iter().position(|x| *x == 1).unwrap_or_default();
+    if !can[0][xi] {
+        return Err(NoAnswer::new(format!(
+            "no program draws {text:?} on a {width}x{} screen",
+            screen.height
+        ))
+        .into());
+    }
+    // Prefer addx, moving X as little as each cycle allows.
+    let mut prog = Vec::new();
+    let mut i = 0;
+    while i < cycles {
+        let next = (0..xs.len())
+            .filter(|n| *n != xi && can[i + 2][*n])
+            .min_by_key(|n| n.abs_diff(xi));
+        match next {
+            Some(n) if draws(i + 1, xi) => {
+                prog.push(Instruction::Addx(xs[n] - xs[xi]));
+                xi = n;
+                i += 2;
+            }
+            _ => {
+                prog.push(Instruction::Noop);
+                i += 1;
+            }
+        }
+    }
+    Ok(prog)
+}
+
+fn enough(cycles: u32, needed: u32) -> Result<()> {
+    if cycles < needed {
+        return Err(NoAnswer::new(format!(
+            "the program stops after {cycles} cycles, needs {needed}"
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+impl Instruction {
+    /// Cycles the instruction takes.
+    #[must_use]
+    pub fn cycles(self) -> u32 {
+        match self {
+            Self::Noop => 1,
+            Self::Addx(_) => 2,
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Noop => write!(f, "noop"),
+            Self::Addx(v) => write!(f, "addx {v}"),
+        }
+    }
+}
+
+/// What the CPU was doing during one cycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tick {
+    /// The cycle, counting from 1.
+    pub cycle: u32,
+    /// X during the cycle.
+    pub x: i32,
+    /// The instruction being run.
+    pub inst: Instruction,
+}
+
+impl Tick {
+    /// Whether the sprite lights the pixel drawn this cycle on a CRT
+    /// `width` pixels wide.
+    #[must_use]
+    pub fn lit(&self, width: u32) -> bool {
+        let col = (self.cycle - 1) % width.max(1);
+        (i64::from(col) - i64::from(self.x)).abs() <= 1
+    }
+}
+
+/// Something watching the CPU cycle by cycle.
+pub trait Observer {
+    /// Called after every cycle the CPU runs.
+    fn tick(&mut self, tick: &Tick);
+}
+
+impl<F: FnMut(&Tick)> Observer for F {
+    fn tick(&mut self, tick: &Tick) {
+        self(tick);
+    }
+}
+
+/// Cycles to sample the signal strength at: `first`, then every `every`
+/// cycles up to `last`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Samples {
+    /// The first cycle sampled.
+    pub first: u32,
+    /// Cycles between samples. 0 samples only `first`.
+    pub every: u32,
+    /// The last cycle that can be sampled.
+    pub last: u32,
+}
+
+impl Samples {
+    /// The samples the puzzle asks for.
+    pub const PUZZLE: Self = Self {
+        first: 20,
+        every: 40,
+        last: 220,
+    };
+
+    /// Whether `cycle` is sampled.
+    #[must_use]
+    pub fn contains(&self, cycle: u32) -> bool {
+        if cycle < self.first || cycle > self.last {
+            return false;
+        }
+        let since = cycle - self.first;
+        since.checked_rem(self.every).unwrap_or(since) == 0
+    }
+}
+
+/// Adds up the signal strength at the sampled cycles.
+#[derive(Debug)]
+pub struct Signal {
+    samples: Samples,
+    sum: i64,
+}
+
+impl Signal {
+    /// Nothing sampled yet.
+    #[must_use]
+    pub fn new(samples: Samples) -> Self {
+        Self { samples, sum: 0 }
+    }
+
+    /// Sum of the signal strengths so far.
+    #[must_use]
+    pub fn sum(&self) -> i64 {
+        self.sum
+    }
+}
+
+impl Observer for Signal {
+    fn tick(&mut self, tick: &Tick) {
+        if self.samples.contains(tick.cycle) {
+            self.sum += i64::from(tick.cycle) * i64::from(tick.x);
+        }
+    }
+}
+
+/// Size of the CRT in pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Screen {
+    /// Pixels drawn per row.
+    pub width: u32,
+    /// Rows drawn.
+    pub height: u32,
+}
+
+impl Screen {
+    /// The puzzle's 40x6 CRT.
+    pub const PUZZLE: Self = Self {
+        width: 40,
+        height: 6,
+    };
+
+    /// Cycles to draw the whole screen.
+    #[must_use]
+    pub fn cycles(&self) -> u32 {
+        self.width.saturating_mul(self.height)
+    }
+}
+
+/// Draws a pixel each cycle until the screen is full.
+#[derive(Debug)]
+pub struct Crt {
+    screen: Screen,
+    picture: String,
+}
+
+impl Crt {
+    /// A blank screen.
+    #[must_use]
+    pub fn new(screen: Screen) -> Self {
+        Self {
+            screen,
+            picture: String::new(),
+        }
+    }
+
+    /// The picture so far, `#` lit and `.` dark, one line per row.
+    #[must_use]
+    pub fn picture(&self) -> &str {
+        &self.picture
+    }
+}
+
+impl Observer for Crt {
+    fn tick(&mut self, tick: &Tick) {
+        if tick.cycle > self.screen.cycles() {
+            return;
+        }
+        self.picture.push(if tick.lit(self.screen.width) {
+            '#'
+        } else {
+            '.'
+        });
+        if tick.cycle % self.screen.width.max(1) == 0 {
+            self.picture.push('\n');
+        }
+    }
+}
+
+/// Where to stop a [`Cpu::run`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// After the given cycle.
+    Cycle(u32),
+    /// After the first cycle of a run of cycles X has this value.
+    X(i32),
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cycle(c) => write!(f, "cycle {c}"),
+            Self::X(x) => write!(f, "X {x}"),
+        }
+    }
+}
+
+/// Why [`Cpu::run`] returned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stop {
+    /// The program ran off its end.
+    Halted,
+    /// A breakpoint was hit on this cycle. Running again carries on from
+    /// the next one.
+    Break(Breakpoint, Tick),
+}
+
+/// The handheld's CPU running a program.
+#[derive(Debug)]
+pub struct Cpu<'a> {
+    prog: &'a [Instruction],
+    // Next instruction and cycles already spent on it.
+    pc: usize,
+    spent: u32,
+    x: i32,
+    cycle: u32,
+    // X during the last cycle run.
+    last_x: Option<i32>,
+    breakpoints: Vec<Breakpoint>,
+}
+
+impl<'a> Cpu<'a> {
+    /// Ready to run `prog` from the start with X at 1.
+    #[must_use]
+    pub fn new(prog: &'a [Instruction]) -> Self {
+        Self {
+            prog,
+            pc: 0,
+            spent: 0,
+            x: 1,
+            cycle: 0,
+            last_x: None,
+            breakpoints: Vec::new(),
+        }
+    }
+
+    /// Stop [`Cpu::run`] at `breakpoint` too.
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.push(breakpoint);
+    }
+
+    /// Cycles run so far.
+    #[must_use]
+    pub fn cycle(&self) -> u32 {
+        self.cycle
+    }
+
+    /// X now, after the cycles run so far.
+    #[must_use]
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    /// Run one cycle. `None` once the program has finished.
+    pub fn step(&mut self) -> Option<Tick> {
+        let inst = *self.prog.get(self.pc)?;
+        self.cycle += 1;
+        let tick = Tick {
+            cycle: self.cycle,
+            x: self.x,
+            inst,
+        };
+        self.spent += 1;
+        if self.spent == inst.cycles() {
+            if let Instruction::Addx(v) = inst {
+                self.x += v;
+            }
+            self.pc += 1;
+            self.spent = 0;
+        }
+        Some(tick)
+    }
+
+    /// Run until the program finishes or hits a breakpoint, showing every
+    /// cycle to the `observers`.
+    pub fn run(&mut self, observers: &mut [&mut dyn Observer]) -> Stop {
+        while let Some(tick) = self.step() {
+            for o in observers.iter_mut() {
+                o.tick(&tick);
+            }
+            let last_x = self.last_x.replace(tick.x);
+            let hit = self.breakpoints.iter().find(|b| match b {
+                Breakpoint::Cycle(c) => *c == tick.cycle,
+                Breakpoint::X(x) => *x == tick.x && last_x != Some(*x),
+            });
+            if let Some(b) = hit {
+                return Stop::Break(*b, tick);
+            }
+        }
+        Stop::Halted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 13140);
+        Ok(())
+    }
+
+    // The example draws stripes rather than letters.
+    #[test]
+    fn part2_example() -> Result<()> {
+        let prog = parse(EXAMPLE)?;
+        assert!(part2(&prog).unwrap_err().is::<NoAnswer>());
+        assert_eq!(
+            picture(&prog)?,
+            "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_letters() -> Result<()> {
+        let picture = "\
+####.#..#.####.####.####.#..#..##..####.
+#....#..#....#.#.......#.#..#.#..#....#.
+###..####...#..###....#..####.#......#..
+#....#..#..#...#.....#...#..#.#.....#...
+#....#..#.#....#....#....#..#.#..#.#....
+####.#..#.####.#....####.#..#..##..####.
+";
+        assert_eq!(ocr(picture)?, "EHZFZHCZ");
+        assert!(ocr(&picture.replacen('#', ".", 1))
+            .unwrap_err()
+            .is::<NoAnswer>());
+        Ok(())
+    }
+
+    #[test]
+    fn step() -> Result<()> {
+        let prog = parse("noop\naddx 3\naddx -5")?;
+        let mut cpu = Cpu::new(&prog);
+        let mut xs = Vec::new();
+        while let Some(tick) = cpu.step() {
+            assert_eq!(tick.cycle, cpu.cycle());
+            xs.push(tick.x);
+        }
+        assert_eq!(xs, [1, 1, 1, 4, 4]);
+        assert_eq!(cpu.x(), -1);
+        Ok(())
+    }
+
+    #[test]
+    fn breakpoints() -> Result<()> {
+        let prog = parse(EXAMPLE)?;
+        let mut cpu = Cpu::new(&prog);
+        cpu.add_breakpoint(Breakpoint::Cycle(20));
+        cpu.add_breakpoint(Breakpoint::X(21));
+        let mut cycles = 0;
+        let mut count = |_: &Tick| cycles += 1;
+        let Stop::Break(b, tick) = cpu.run(&mut [&mut count]) else {
+            panic!("no break");
+        };
+        // X first becomes 21 during cycle 19.
+        assert_eq!((b, tick.cycle, tick.x), (Breakpoint::X(21), 19, 21));
+        let Stop::Break(b, tick) = cpu.run(&mut [&mut count]) else {
+            panic!("no break");
+        };
+        assert_eq!((b, tick.x), (Breakpoint::Cycle(20), 21));
+        let mut later = Vec::new();
+        while let Stop::Break(b, tick) = cpu.run(&mut [&mut count]) {
+            later.push((b, tick.cycle));
+        }
+        assert_eq!(later, [(Breakpoint::X(21), 140), (Breakpoint::X(21), 212)]);
+        assert_eq!(cycles, 240);
+        Ok(())
+    }
+
+    #[test]
+    fn parameters() -> Result<()> {
+        let prog = parse(EXAMPLE)?;
+        let samples = Samples {
+            first: 20,
+            every: 0,
+            last: 20,
+        };
+        assert_eq!(signal_strength(&prog, samples)?, 420);
+        let screen = Screen {
+            width: 8,
+            height: 2,
+        };
+        assert_eq!(draw(&prog, screen)?, "##..##..\n........\n");
+        let screen = Screen {
+            width: 0,
+            height: 6,
+        };
+        assert_eq!(draw(&prog, screen)?, "");
+        Ok(())
+    }
+
+    #[test]
+    fn assembler() -> Result<()> {
+        for text in ["EHZFZHCZ", "BLURP", "ZO LO", "ZZZZZZZZ"] {
+            let prog = assemble(text, Screen::PUZZLE)?;
+            let listing = prog.iter().map(ToString::to_string).collect::<Vec<_>>();
+            let prog = parse(&listing.join("\n"))?;
+            assert_eq!(picture(&prog)?, render(text, Screen::PUZZLE)?);
+            assert_eq!(part2(&prog)?, text);
+        }
+        // The first two pixels are always lit, A's and L's aren't.
+        for text in ["A", "L", "", "EHZFZHCZE", "e"] {
+            assert!(assemble(text, Screen::PUZZLE).unwrap_err().is::<NoAnswer>());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn short_program() -> Result<()> {
+        let prog = parse(&EXAMPLE.lines().take(100).collect::<Vec<_>>().join("\n"))?;
+        assert!(part1(&prog).unwrap_err().is::<NoAnswer>());
+        assert!(part2(&prog).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+}
@@ -1,67 +1,135 @@
 //! day10 advent 2022
+use clap::{Parser, Subcommand};
 use color_eyre::eyre::Result;
-use std::fmt::Write;
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
-use std::path::Path;
+use day10::{
+    assemble, draw, ocr, parse, signal_strength, Breakpoint, Cpu, Crt, Samples, Screen, Stop, Tick,
+};
+
+#[derive(Parser)]
+#[command(author, version, about)]
+struct Args {
+    #[command(flatten)]
+    common: aoc_common::Args,
+
+    /// Show the CRT picture for part 2 instead of reading the letters.
+    #[arg(long, default_value_t = false)]
+    picture: bool,
+
+    /// Print the cycle, instruction, X and pixel drawn for every cycle.
+    #[arg(long, default_value_t = false)]
+    trace: bool,
+
+    /// Stop after this cycle and show the CRT so far. Can be repeated.
+    #[arg(long)]
+    break_cycle: Vec<u32>,
+
+    /// Stop when X takes this value and show the CRT so far. Can be
+    /// repeated.
+    #[arg(long, allow_negative_numbers = true)]
+    break_x: Vec<i32>,
+
+    /// First cycle to sample the signal strength at.
+    #[arg(long, default_value_t = Samples::PUZZLE.first)]
+    first_sample: u32,
+
+    /// Cycles between signal strength samples.
+    #[arg(long, default_value_t = Samples::PUZZLE.every)]
+    sample_every: u32,
+
+    /// Last cycle to sample the signal strength at.
+    #[arg(long, default_value_t = Samples::PUZZLE.last)]
+    last_sample: u32,
+
+    /// CRT width in pixels.
+    #[arg(long, default_value_t = Screen::PUZZLE.width)]
+    width: u32,
+
+    /// CRT height in pixels.
+    #[arg(long, default_value_t = Screen::PUZZLE.height)]
+    height: u32,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print a program that draws TEXT on the CRT instead of solving.
+    Assemble {
+        /// Capital letters and spaces to draw. The first letter's top row
+        /// has to start `##`, like E's.
+        text: String,
+    },
+}
 
 fn main() -> Result<()> {
-    let filename = Path::new(env!("CARGO_MANIFEST_DIR")).join("input.txt");
-    let file = File::open(filename)?;
-    let lines: Vec<String> = io::BufReader::new(file).lines().flatten().collect();
-
-    let mut x = 1;
-    let mut cycles = 0;
-    let mut sum = 0;
-    let mut pixel = 0;
-    let mut led = String::new();
-    for (line_num, line) in lines.iter().enumerate() {
-        let parts: Vec<&str> = line.split_whitespace().collect();
-
-        assert!(
-            parts.len() == 1 || parts.len() == 2,
-            "{} - bad line {line}",
-            line_num + 1
-        );
-
-        let mut run: usize = 1;
-        let mut add = None;
-        match *parts.first().unwrap() {
-            "noop" => {}
-            "addx" => {
-                run = 2;
-                let val = parts[1].parse::<i32>()?;
-                add = Some(val);
-            }
-            _ => {
-                panic!("{} - base line {line}", line_num + 1);
-            }
+    color_eyre::install()?;
+    let args: Args = Args::parse();
+    let report = args.common.report(10);
+    let samples = Samples {
+        first: args.first_sample,
+        every: args.sample_every,
+        last: args.last_sample,
+    };
+    let screen = Screen {
+        width: args.width.max(1),
+        height: args.height,
+    };
+
+    if let Some(Command::Assemble { text }) = &args.command {
+        for inst in assemble(text, screen)? {
+            println!("{inst}");
         }
-        for _ in 0..run {
-            cycles += 1;
-            if cycles == 20 || (cycles > 20 && (cycles - 20) % 40 == 0) {
-                sum += cycles * x;
-            }
-            let mut out = ".";
-            if pixel >= x - 1 && pixel <= x + 1 {
-                out = "#";
-            }
-            write!(led, "{out}")?;
-            if cycles % 40 == 0 {
-                writeln!(led)?;
-            }
-            pixel += 1;
-            if pixel >= 40 {
-                pixel = 0;
-            }
+        return Ok(());
+    }
+
+    let file = args.common.input(env!("CARGO_MANIFEST_DIR"))?;
+    let prog = file.parse(parse)?;
+    if args.common.debug {
+        report.show(format_args!("{} instructions", prog.len()));
+    }
+
+    if args.trace || !args.break_cycle.is_empty() || !args.break_x.is_empty() {
+        let mut cpu = Cpu::new(&prog);
+        for c in &args.break_cycle {
+            cpu.add_breakpoint(Breakpoint::Cycle(*c));
         }
-        if let Some(v) = add {
-            x += v;
+        for x in &args.break_x {
+            cpu.add_breakpoint(Breakpoint::X(*x));
         }
+        let mut crt = Crt::new(screen);
+        let mut trace = |t: &Tick| {
+            if args.trace {
+                let pixel = if t.lit(screen.width) { '#' } else { '.' };
+                report.show(format_args!(
+                    "cycle {:>4}  {:<10} X {:>4}  pixel {pixel}",
+                    t.cycle,
+                    t.inst.to_string(),
+                    t.x
+                ));
+            }
+        };
+        while let Stop::Break(b, t) = cpu.run(&mut [&mut trace, &mut crt]) {
+            report.show(format_args!(
+                "break ({b}) at cycle {}: X {} during, {} after",
+                t.cycle,
+                t.x,
+                cpu.x()
+            ));
+            report.show(crt.picture().trim_end());
+        }
+        report.show(format_args!(
+            "halted after {} cycles with X {}",
+            cpu.cycle(),
+            cpu.x()
+        ));
+    }
+
+    report.part(1, "part1 - ", || signal_strength(&prog, samples))?;
+    if args.picture {
+        report.part(2, "part2 -\n", || draw(&prog, screen))?;
+    } else {
+        report.part(2, "part2 - ", || ocr(&draw(&prog, screen)?))?;
     }
-    println!("part1 - {sum}");
-    println!("part2 -");
-    print!("{led}");
     Ok(())
 }
@@ -0,0 +1,2 @@
+//! day11 benchmarks.
+aoc_common::benches!(day11, [part1, part2]);
@@ -0,0 +1,743 @@
+//! day11 advent 2022
+use aoc_common::{lines, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use std::fmt;
+
+/// How a monkey changes the worry level of an item: the right hand side of
+/// `new = ...`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    /// The item's current worry level.
+    Old,
+    /// A literal.
+    Val(i128),
+    /// `a + b`
+    Add(Box<Expr>, Box<Expr>),
+    /// `a - b`
+    Sub(Box<Expr>, Box<Expr>),
+    /// `a * b`
+    Mul(Box<Expr>, Box<Expr>),
+    /// `a / b`, rounding toward zero.
+    Div(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// The new worry level for an item at `old`. `None` if it overflows or
+    /// divides by zero.
+    #[must_use]
+    pub fn eval(&self, old: i128) -> Option<i128> {
+        match self {
+            Self::Old => Some(old),
+            Self::Val(v) => Some(*v),
+            Self::Add(a, b) => a.eval(old)?.checked_add(b.eval(old)?),
+            Self::Sub(a, b) => a.eval(old)?.checked_sub(b.eval(old)?),
+            Self::Mul(a, b) => a.eval(old)?.checked_mul(b.eval(old)?),
+            Self::Div(a, b) => a.eval(old)?.checked_div(b.eval(old)?),
+        }
+    }
+
+    /// Whether worry levels can be kept modulo the tests' divisors. Adding,
+    /// subtracting and multiplying give the same remainder whatever
+    /// multiple of the modulus is taken off `old` first, dividing doesn't.
+    #[must_use]
+    pub fn modular(&self) -> bool {
+        match self {
+            Self::Old | Self::Val(_) => true,
+            Self::Add(a, b) | Self::Sub(a, b) | Self::Mul(a, b) => a.modular() && b.modular(),
+            Self::Div(..) => false,
+        }
+    }
+
+    // How tightly the expression binds, for deciding on parentheses.
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Add(..) | Self::Sub(..) => 1,
+            Self::Mul(..) | Self::Div(..) => 2,
+            Self::Old | Self::Val(_) => 3,
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (a, op, b) = match self {
+            Self::Old => return write!(f, "old"),
+            Self::Val(v) => return write!(f, "{v}"),
+            Self::Add(a, b) => (a, '+', b),
+            Self::Sub(a, b) => (a, '-', b),
+            Self::Mul(a, b) => (a, '*', b),
+            Self::Div(a, b) => (a, '/', b),
+        };
+        let p = self.precedence();
+        if a.precedence() < p {
+            write!(f, "({a})")?;
+        } else {
+            write!(f, "{a}")?;
+        }
+        write!(f, " {op} ")?;
+        // Everything here is left associative so an equal right side needs
+        // them too.
+        if b.precedence() <= p {
+            write!(f, "({b})")
+        } else {
+            write!(f, "{b}")
+        }
+    }
+}
+
+/// One monkey and the items it's holding.
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    /// Worry levels of the items held.
+    pub items: Vec<i128>,
+    /// Operation applied on inspection.
+    pub op: Expr,
+    /// Divisor for the throw test.
+    pub test: i128,
+    /// Monkey to throw to when the test passes and when it fails.
+    pub choice: [usize; 2],
+    /// Number of items inspected so far.
+    pub inspected: u64,
+    /// Number of items thrown to each of `choice` so far.
+    pub thrown: [u64; 2],
+}
+
+/// The monkeys in order.
+pub type Input = Vec<Monkey>;
+
+/// Parse the monkey notes.
+///
+/// # Errors
+/// On a truncated or malformed monkey.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut monkeys = Vec::new();
+    let mut it = lines(input);
+    while let Some((line_num, line)) = it.next() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        parse_monkey(&mut it, &parts, &mut monkeys, line, line_num)?;
+    }
+    Ok(monkeys)
+}
+
+/// Monkey business after 20 rounds with relief.
+///
+/// # Errors
+/// [`NoAnswer`] with fewer than 2 monkeys or if a monkey throws somewhere
+/// it can't.
+pub fn part1(monkeys: &Input) -> Result<u128> {
+    business(&inspections(monkeys, 20, true)?)
+}
+
+/// Monkey business after 10000 rounds without relief.
+///
+/// # Errors
+/// Same as [`part1`].
+pub fn part2(monkeys: &Input) -> Result<u128> {
+    business(&inspections(monkeys, 10_000, false)?)
+}
+
+/// Monkey business from the inspection counts [`inspections`] returns.
+///
+/// # Errors
+/// [`NoAnswer`] with fewer than 2 monkeys.
+pub fn business(inspected: &[u64]) -> Result<u128> {
+    match inspected {
+        [a, b, ..] => Ok(u128::from(*a) * u128::from(*b)),
+        _ => Err(NoAnswer::new(format!("need 2 monkeys, only have {}", inspected.len())).into()),
+    }
+}
+
+/// Play `rounds` rounds and return how many items each monkey inspected,
+/// largest first. `divide` is whether worry is divided by 3 after each
+/// inspection. Without it worry is kept modulo the product of the tests'
+/// divisors.
+///
+/// # Errors
+/// [`NoAnswer`] if a monkey throws to itself or to a monkey that doesn't
+/// exist, if an operation overflows or divides by zero, or if an operation
+/// isn't [`Expr::modular`] when `divide` is off.
+pub fn inspections(monkeys: &[Monkey], rounds: usize, divide: bool) -> Result<Vec<u64>> {
+    let mut inspected = Vec::new();
+    for monkey in &simulate(monkeys, rounds, divide)? {
+        inspected.push(monkey.inspected);
+    }
+    inspected.sort_unstable();
+    inspected.reverse();
+    Ok(inspected)
+}
+
+/// The monkeys after playing `rounds` rounds, as for [`inspections`], with
+/// the items they're left holding and their counts filled in.
+///
+/// # Errors
+/// Same as [`inspections`].
+pub fn simulate(monkeys: &[Monkey], rounds: usize, divide: bool) -> Result<Vec<Monkey>> {
+    let lcm = check(monkeys, divide)?;
+
+    // Make a copy since we change it for each run below.
+    let mut monkeys = monkeys.to_vec();
+    for _ in 0..rounds {
+        for i in 0..monkeys.len() {
+            let monkey = &mut monkeys[i];
+            let mut new = Vec::new();
+            for item in &monkey.items {
+                let (index, worry) = throw(monkey, i, *item, divide, lcm)?;
+                new.push((index, worry));
+                monkey.inspected += 1;
+                monkey.thrown[usize::from(index != monkey.choice[0])] += 1;
+            }
+            monkey.items.clear();
+            for (index, worry) in new {
+                monkeys[index].items.push(worry);
+            }
+        }
+    }
+    Ok(monkeys)
+}
+
+/// The throws between `monkeys` as a Graphviz digraph called `name`. Each
+/// monkey is labelled with its operation, test and inspections and each
+/// throw with how many items went that way, drawn thicker the busier it
+/// is. A monkey that throws to the same one either way gets a single edge
+/// with both counts added. Counts come from the monkeys, so pass ones from
+/// [`simulate`].
+#[must_use]
+pub fn dot(name: &str, monkeys: &[Monkey]) -> String {
+    let edges = monkeys
+        .iter()
+        .map(|m| {
+            if m.choice[0] == m.choice[1] {
+                vec![(m.choice[0], "either", m.thrown[0] + m.thrown[1])]
+            } else {
+                vec![
+                    (m.choice[0], "true", m.thrown[0]),
+                    (m.choice[1], "false", m.thrown[1]),
+                ]
+            }
+        })
+        .collect::<Vec<_>>();
+    let busiest = edges
+        .iter()
+        .flatten()
+        .map(|e| e.2)
+        .max()
+        .unwrap_or_default()
+        .max(1);
+    let mut lines = vec![
+        format!("digraph {name} {{"),
+        String::from("    node [shape=box];"),
+    ];
+    for (i, m) in monkeys.iter().enumerate() {
+        lines.push(format!(
+            "    m{i} [label=\"Monkey {i}\\nnew = {}\\ndivisible by {}\\ninspected {}\"];",
+            m.op, m.test, m.inspected
+        ));
+    }
+    for (i, throws) in edges.iter().enumerate() {
+        for (to, test, count) in throws {
+            // Only sets the line thickness, so precision lost on huge counts
+            // doesn't matter.
+            #[allow(clippy::cast_precision_loss)]
+            let width = 1.0 + 4.0 * *count as f64 / busiest as f64;
+            lines.push(format!(
+                "    m{i} -> m{to} [label=\"{test}: {count}\", penwidth={width:.1}];"
+            ));
+        }
+    }
+    lines.push(String::from("}\n"));
+    lines.join("\n")
+}
+
+/// The same counts as [`inspections`] but worked out by following each item
+/// on its own, so `rounds` can be as large as `10^12`.
+///
+/// An item's path only depends on which monkey has it and its worry level
+/// at the start of a round. Without relief those are bounded, so the path
+/// ends up in a cycle. Once that's found the rounds left over are whole
+/// trips around it plus a remainder. If the cycle takes longer to find
+/// than `rounds` the item is just played out.
+///
+/// # Errors
+/// Same as [`inspections`].
+pub fn trajectories(monkeys: &[Monkey], rounds: u64, divide: bool) -> Result<Vec<u64>> {
+    let lcm = check(monkeys, divide)?;
+    // Where an item is at the start of the next round, counting the
+    // inspections along the way.
+    let round = |(mut at, mut worry): (usize, i128), counts: &mut [u64]| -> Result<_> {
+        loop {
+            counts[at] += 1;
+            let (to, new) = throw(&monkeys[at], at, worry, divide, lcm)?;
+            worry = new;
+            // Monkeys later in the order get to it this round.
+            if to < at {
+                return Ok((to, worry));
+            }
+            at = to;
+        }
+    };
+    // Play `n` rounds from `state`.
+    let play = |mut state, n: u64, counts: &mut [u64]| -> Result<_> {
+        for _ in 0..n {
+            state = round(state, counts)?;
+        }
+        Ok(state)
+    };
+
+    let mut inspected = vec![0; monkeys.len()];
+    let mut scratch = vec![0; monkeys.len()];
+    for (i, monkey) in monkeys.iter().enumerate() {
+        for item in &monkey.items {
+            let start = (i, *item);
+            let Some((lead, cycle)) = find_cycle(start, rounds, |s| round(s, &mut scratch))? else {
+                play(start, rounds, &mut inspected)?;
+                continue;
+            };
+            let mut before = vec![0; monkeys.len()];
+            let mut around = vec![0; monkeys.len()];
+            let entry = play(start, lead, &mut before)?;
+            play(entry, cycle, &mut around)?;
+            let left = rounds - lead;
+            play(entry, left % cycle, &mut before)?;
+            for (total, (b, a)) in inspected.iter_mut().zip(before.iter().zip(&around)) {
+                *total += b + a * (left / cycle);
+            }
+        }
+    }
+    inspected.sort_unstable();
+    inspected.reverse();
+    Ok(inspected)
+}
+
+// Brent's algorithm. The rounds before `start` enters a cycle and the
+// cycle's length, or `None` if that takes more than `limit` rounds to find.
+fn find_cycle<S: Copy + PartialEq>(
+    start: S,
+    limit: u64,
+    mut next: impl FnMut(S) -> Result<S>,
+) -> Result<Option<(u64, u64)>> {
+    let mut power = 1;
+    let mut cycle = 1;
+    let mut tortoise = start;
+    let mut hare = next(start)?;
+    let mut steps = 1;
+    while tortoise != hare {
+        if steps > limit {
+            return Ok(None);
+        }
+        if power == cycle {
+            tortoise = hare;
+            power *= 2;
+            cycle = 0;
+        }
+        hare = next(hare)?;
+        cycle += 1;
+        steps += 1;
+    }
+    let mut tortoise = start;
+    let mut hare = start;
+    for _ in 0..cycle {
+        hare = next(hare)?;
+    }
+    let mut lead = 0;
+    while tortoise != hare {
+        tortoise = next(tortoise)?;
+        hare = next(hare)?;
+        lead += 1;
+    }
+    Ok(Some((lead, cycle)))
+}
+
+// Make sure every monkey throws somewhere it can and returns what worry is
+// kept modulo when not dividing.
+fn check(monkeys: &[Monkey], divide: bool) -> Result<i128> {
+    for (i, monkey) in monkeys.iter().enumerate() {
+        for c in monkey.choice {
+            if c == i || c >= monkeys.len() {
+                return Err(NoAnswer::new(format!("monkey {i} throws to monkey {c}")).into());
+            }
+        }
+        if !divide && !monkey.op.modular() {
+            return Err(NoAnswer::new(format!(
+                "monkey {i}'s operation `new = {}` divides so worry can't be kept \
+                 modulo the tests",
+                monkey.op
+            ))
+            .into());
+        }
+    }
+    // Dividing by 3 keeps worry down on its own, the modulus isn't used.
+    if divide {
+        return Ok(1);
+    }
+    let mut lcm: i128 = 1;
+    for (i, monkey) in monkeys.iter().enumerate() {
+        let Some(next) = lcm.checked_mul(monkey.test) else {
+            return Err(NoAnswer::new(format!(
+                "the tests overflow multiplied together at monkey {i}'s {}",
+                monkey.test
+            ))
+            .into());
+        };
+        lcm = next;
+    }
+    Ok(lcm)
+}
+
+// Monkey `i` inspects an item, returning who it's thrown to and the new
+// worry level.
+fn throw(monkey: &Monkey, i: usize, item: i128, divide: bool, lcm: i128) -> Result<(usize, i128)> {
+    let Some(mut worry) = monkey.op.eval(item) else {
+        return Err(NoAnswer::new(format!(
+            "monkey {i}'s operation `new = {}` overflows or divides by zero for \
+             worry level {item}",
+            monkey.op
+        ))
+        .into());
+    };
+    if divide {
+        worry = worry.div_euclid(3);
+    } else {
+        worry = worry.rem_euclid(lcm);
+    }
+    let index = if worry % monkey.test == 0 {
+        monkey.choice[0]
+    } else {
+        monkey.choice[1]
+    };
+    Ok((index, worry))
+}
+
+// Each line of a monkey after the first starts with a fixed label. Returns
+// the next line's number and text.
+fn labelled_line<'a>(
+    it: &mut std::iter::Enumerate<std::str::Lines<'a>>,
+    prev: (usize, &str),
+    label: &str,
+) -> Result<(usize, &'a str)> {
+    let Some((line_num, line)) = it.next() else {
+        return Err(ParseError::end_of_input(prev.0, prev.1, format!("`{label}`")).into());
+    };
+    if !line.trim_start().starts_with(label) {
+        return Err(ParseError::new(line_num, line, line.trim(), format!("`{label}`")).into());
+    }
+    Ok((line_num, line))
+}
+
+// A labelled line with a fixed number of fields. Returns the line's number,
+// text and fields.
+fn monkey_line<'a>(
+    it: &mut std::iter::Enumerate<std::str::Lines<'a>>,
+    prev: (usize, &str),
+    label: &str,
+    fields: usize,
+) -> Result<(usize, &'a str, Vec<&'a str>)> {
+    let (line_num, line) = labelled_line(it, prev, label)?;
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    if parts.len() < fields {
+        return Err(ParseError::end_of_line(line_num, line, "more fields").into());
+    }
+    if let Some(extra) = parts.get(fields) {
+        return Err(ParseError::new(line_num, line, extra, "end of line").into());
+    }
+    Ok((line_num, line, parts))
+}
+
+fn parse_monkey(
+    it: &mut std::iter::Enumerate<std::str::Lines<'_>>,
+    parts: &[&str],
+    monkeys: &mut Vec<Monkey>,
+    line: &str,
+    line_num: usize,
+) -> Result<()> {
+    let Some(first) = parts.first() else {
+        return Ok(());
+    };
+    if *first != "Monkey" {
+        return Err(ParseError::new(line_num, line, first, "`Monkey`").into());
+    }
+    let mut monkey = Monkey {
+        items: Vec::new(),
+        op: Expr::Old,
+        test: 0,
+        choice: [0, 0],
+        inspected: 0,
+        thrown: [0, 0],
+    };
+
+    // Starting items: x, y
+    let Some((line_num, line)) = it.next() else {
+        return Err(ParseError::end_of_input(line_num, line, "`Starting items:`").into());
+    };
+    let Some((_, items)) = line.split_once("Starting items:") else {
+        return Err(ParseError::new(line_num, line, line.trim(), "`Starting items:`").into());
+    };
+    for item in items.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+        monkey
+            .items
+            .push(ParseError::parse(line_num, line, item, "a worry level")?);
+    }
+
+    // Operation: new = old * (old + 3)
+    let (line_num, line) = labelled_line(it, (line_num, line), "Operation:")?;
+    let Some((_, rhs)) = line.split_once("new =") else {
+        let found = line.trim_start()["Operation:".len()..].trim();
+        return Err(ParseError::new(line_num, line, found, "`new =`").into());
+    };
+    let mut tokens = tokens(rhs).peekable();
+    monkey.op = parse_expr(&mut tokens, line_num, line)?;
+    if let Some(extra) = tokens.next() {
+        return Err(
+            ParseError::new(line_num, line, extra, "`+`, `-`, `*`, `/` or end of line").into(),
+        );
+    }
+
+    // Test: divisible by 17
+    let (line_num, line, parts) = monkey_line(it, (line_num, line), "Test:", 4)?;
+    monkey.test = ParseError::parse(line_num, line, parts[3], "a divisor")?;
+    if monkey.test <= 0 {
+        return Err(ParseError::new(line_num, line, parts[3], "a positive divisor").into());
+    }
+
+    // If true: throw to monkey 4
+    let (line_num, line, parts) = monkey_line(it, (line_num, line), "If true:", 6)?;
+    monkey.choice[0] = ParseError::parse(line_num, line, parts[5], "a monkey number")?;
+
+    // If false: throw to monkey 5
+    let (line_num, line, parts) = monkey_line(it, (line_num, line), "If false:", 6)?;
+    monkey.choice[1] = ParseError::parse(line_num, line, parts[5], "a monkey number")?;
+
+    monkeys.push(monkey);
+    Ok(())
+}
+
+type Tokens<'a> = std::iter::Peekable<Box<dyn Iterator<Item = &'a str> + 'a>>;
+
+// Split an expression into numbers, words and single character symbols,
+// each a slice of `text`.
+fn tokens(text: &str) -> Box<dyn Iterator<Item = &str> + '_> {
+    let mut rest = text.trim_start();
+    Box::new(std::iter::from_fn(move || {
+        let first = rest.chars().next()?;
+        let len = if first.is_ascii_alphanumeric() {
+            rest.find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(rest.len())
+        } else {
+            first.len_utf8()
+        };
+        let (token, after) = rest.split_at(len);
+        rest = after.trim_start();
+        Some(token)
+    }))
+}
+
+// expr := term (('+' | '-') term)*
+fn parse_expr(tokens: &mut Tokens<'_>, line_num: usize, line: &str) -> Result<Expr> {
+    let mut expr = parse_term(tokens, line_num, line)?;
+    while let Some(op) = tokens.next_if(|t| *t == "+" || *t == "-") {
+        let rhs = Box::new(parse_term(tokens, line_num, line)?);
+        expr = if op == "+" {
+            Expr::Add(Box::new(expr), rhs)
+        } else {
+            Expr::Sub(Box::new(expr), rhs)
+        };
+    }
+    Ok(expr)
+}
+
+// term := factor (('*' | '/') factor)*
+fn parse_term(tokens: &mut Tokens<'_>, line_num: usize, line: &str) -> Result<Expr> {
+    let mut expr = parse_factor(tokens, line_num, line)?;
+    while let Some(op) = tokens.next_if(|t| *t == "*" || *t == "/") {
+        let rhs = Box::new(parse_factor(tokens, line_num, line)?);
+        expr = if op == "*" {
+            Expr::Mul(Box::new(expr), rhs)
+        } else {
+            Expr::Div(Box::new(expr), rhs)
+        };
+    }
+    Ok(expr)
+}
+
+// factor := 'old' | number | '(' expr ')'
+fn parse_factor(tokens: &mut Tokens<'_>, line_num: usize, line: &str) -> Result<Expr> {
+    const WANT: &str = "`old`, a number or `(`";
+    let Some(token) = tokens.next() else {
+        return Err(ParseError::end_of_line(line_num, line, WANT).into());
+    };
+    match token {
+        "old" => Ok(Expr::Old),
+        "(" => {
+            let expr = parse_expr(tokens, line_num, line)?;
+            match tokens.next() {
+                Some(")") => Ok(expr),
+                Some(t) => Err(ParseError::new(line_num, line, t, "`)`").into()),
+                None => Err(ParseError::end_of_line(line_num, line, "`)`").into()),
+            }
+        }
+        t if t.starts_with(|c: char| c.is_ascii_digit()) => {
+            Ok(Expr::Val(ParseError::parse(line_num, line, t, "a number")?))
+        }
+        t => Err(ParseError::new(line_num, line, t, WANT).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 10605);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 2_713_310_158);
+        Ok(())
+    }
+
+    #[test]
+    fn trajectories_match() -> Result<()> {
+        let monkeys = parse(EXAMPLE)?;
+        for (rounds, divide) in [(20, true), (10_000, false), (12_345, false)] {
+            assert_eq!(
+                trajectories(&monkeys, rounds, divide)?,
+                inspections(&monkeys, usize::try_from(rounds)?, divide)?,
+                "{rounds} {divide}"
+            );
+        }
+        // Every item is inspected at least once a round.
+        let huge = trajectories(&monkeys, 1_000_000_000_000, false)?;
+        assert!(huge.iter().sum::<u64>() >= 10 * 1_000_000_000_000);
+        assert!(business(&huge)? > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn graph() -> Result<()> {
+        let monkeys = simulate(&parse(EXAMPLE)?, 20, true)?;
+        for m in &monkeys {
+            assert_eq!(m.thrown.iter().sum::<u64>(), m.inspected);
+        }
+        let dot = dot("part1", &monkeys);
+        assert!(dot.starts_with("digraph part1 {\n"));
+        assert!(dot.contains(
+            "    m0 [label=\"Monkey 0\\nnew = old * 19\\ndivisible by 23\\ninspected 101\"];\n"
+        ));
+        assert!(dot.contains("    m3 -> m0 [label=\"true: "));
+        assert_eq!(dot.matches(" -> ").count(), 8);
+        Ok(())
+    }
+
+    #[test]
+    fn graph_same_target() -> Result<()> {
+        // Monkey 0 throws to 3 whichever way the test goes.
+        let input = EXAMPLE.replacen(
+            "If true: throw to monkey 2",
+            "If true: throw to monkey 3",
+            1,
+        );
+        let monkeys = simulate(&parse(&input)?, 20, true)?;
+        let dot = dot("same", &monkeys);
+        assert!(dot.contains(&format!(
+            "    m0 -> m3 [label=\"either: {}\", penwidth=",
+            monkeys[0].inspected
+        )));
+        assert_eq!(dot.matches("    m0 -> ").count(), 1);
+        assert_eq!(dot.matches(" -> ").count(), 7);
+        Ok(())
+    }
+
+    #[test]
+    fn truncated_monkey() -> Result<()> {
+        let monkey = EXAMPLE.lines().take(4).collect::<Vec<_>>().join("\n");
+        let err = parse(&monkey).unwrap_err().downcast::<ParseError>()?;
+        assert_eq!(err.line, 4);
+        assert_eq!(err.expected, "`If true:`");
+        assert_eq!(err.found, "end of input");
+        Ok(())
+    }
+
+    #[test]
+    fn bad_operator() -> Result<()> {
+        let input = EXAMPLE.replacen("old * 19", "old % 19", 1);
+        let err = parse(&input).unwrap_err().downcast::<ParseError>()?;
+        assert_eq!((err.line, err.column), (3, 24));
+        assert_eq!(err.expected, "`+`, `-`, `*`, `/` or end of line");
+
+        for (op, column, expected) in [
+            ("old +", 25, "`old`, a number or `(`"),
+            ("(old * 2", 28, "`)`"),
+            ("old * x", 26, "`old`, a number or `(`"),
+            ("old old", 24, "`+`, `-`, `*`, `/` or end of line"),
+        ] {
+            let input = EXAMPLE.replacen("old * 19", op, 1);
+            let err = parse(&input).unwrap_err().downcast::<ParseError>()?;
+            assert_eq!((err.line, err.column), (3, column), "{op}");
+            assert_eq!(err.expected, expected, "{op}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn expressions() -> Result<()> {
+        for (op, old, new) in [
+            ("old * old + 3", 4, 19),
+            ("old - 2", 1, -1),
+            ("(old - 2) * (3 + old)", 5, 24),
+            ("old - (old - 1)", 7, 1),
+            ("2 * 3 - old / 2", 5, 4),
+        ] {
+            let input = EXAMPLE.replacen("old * 19", op, 1);
+            let expr = &parse(&input)?[0].op;
+            assert_eq!(expr.eval(old), Some(new), "{op}");
+            assert_eq!(expr.to_string(), op);
+            assert_eq!(expr.modular(), !op.contains('/'));
+        }
+
+        // Relief copes with dividing, keeping worry modulo the tests doesn't.
+        let input = EXAMPLE.replacen("old * 19", "old * 38 / 2", 1);
+        let monkeys = parse(&input)?;
+        assert_eq!(part1(&monkeys)?, 10605);
+        assert!(part2(&monkeys).unwrap_err().is::<NoAnswer>());
+
+        // Negative worry levels are fine either way.
+        let input = EXAMPLE.replacen("old * 19", "old * 0 - 1", 1);
+        assert!(part1(&parse(&input)?).is_ok());
+        assert!(part2(&parse(&input)?).is_ok());
+        let input = EXAMPLE.replacen("old * 19", "old / 0", 1);
+        assert!(part1(&parse(&input)?).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+
+    #[test]
+    fn huge_tests() -> Result<()> {
+        let mut input = String::from(EXAMPLE);
+        for d in ["23", "19", "13", "17"] {
+            input = input.replace(
+                &format!("divisible by {d}\n"),
+                &format!("divisible by {d}{}\n", "0".repeat(10)),
+            );
+        }
+        let monkeys = parse(&input)?;
+        assert!(part1(&monkeys).is_ok());
+        assert!(part2(&monkeys).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+
+    #[test]
+    fn single_monkey() -> Result<()> {
+        let monkey = EXAMPLE.lines().take(6).collect::<Vec<_>>().join("\n");
+        let monkey = monkey
+            .replace("monkey 2", "monkey 1")
+            .replace("monkey 3", "monkey 1");
+        let monkeys = parse(&monkey)?;
+        assert!(part1(&monkeys).unwrap_err().is::<NoAnswer>());
+        assert!(business(&[4]).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+//! day12 benchmarks.
+aoc_common::benches!(day12, [part1, part2]);
@@ -0,0 +1,192 @@
+//! day12 advent 2022
+use aoc_common::{lines, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use pathfinding::prelude::astar;
+
+/// An x,y position on the map.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Location(pub usize, pub usize);
+
+impl Location {
+    fn distance(&self, other: &Location) -> u32 {
+        (self.0.abs_diff(other.0) + self.1.abs_diff(other.1))
+            .try_into()
+            .unwrap()
+    }
+
+    fn successors(&self, grid: &[Vec<u8>]) -> Vec<(Self, u32)> {
+        let (x, y): (i32, i32) = (self.0.try_into().unwrap(), self.1.try_into().unwrap());
+        let mut v = Vec::new();
+        let here = u32::from(grid[self.1][self.0]);
+        for i in [(x, y + 1), (x + 1, y), (x - 1, y), (x, y - 1)] {
+            if i.0 >= grid[0].len().try_into().unwrap()
+                || i.1 >= grid.len().try_into().unwrap()
+                || i.0 < 0
+                || i.1 < 0
+            {
+                continue;
+            }
+            #[allow(clippy::cast_sign_loss)]
+            let (x, y) = (i.0 as usize, i.1 as usize);
+            let val = u32::from(grid[y][x]);
+            // Technically this can be handled with BFS so we're just reducing to that
+            // by only adding nodes we like, not all nodes.
+            if val <= here + 1 {
+                v.push((Self(x, y), 1));
+            }
+        }
+        v
+    }
+}
+
+/// The heightmap with its start and end.
+#[derive(Debug)]
+pub struct Input {
+    /// Heights 1 (a) to 26 (z) indexed by `[y][x]`.
+    pub grid: Vec<Vec<u8>>,
+    /// The start (S).
+    pub begin: Location,
+    /// The best signal (E).
+    pub end: Location,
+}
+
+/// Parse the heightmap.
+///
+/// # Errors
+/// If the lines aren't all the same length or hold something other than a
+/// height, `S` or `E`.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut line_len = 0;
+    let mut grid = Vec::new();
+    let mut begin = None;
+    let mut end = None;
+    let mut last = (0, "");
+    for (line_num, line) in lines(input) {
+        last = (line_num, line);
+        if line_num == 0 {
+            line_len = line.len();
+        } else {
+            if line.len() > line_len {
+                return Err(ParseError::new(
+                    line_num,
+                    line,
+                    line.get(line_len..).unwrap_or(line),
+                    "end of line",
+                )
+                .into());
+            }
+            if line.len() < line_len {
+                return Err(
+                    ParseError::end_of_line(line_num, line, format!("{line_len} heights")).into(),
+                );
+            }
+        }
+
+        let mut entry = Vec::new();
+        for (pos, c) in line.as_bytes().iter().enumerate() {
+            match c {
+                b'S' => {
+                    begin = Some(Location(pos, grid.len()));
+                    entry.push(1);
+                }
+                b'E' => {
+                    end = Some(Location(pos, grid.len()));
+                    entry.push(26);
+                }
+                b'a'..=b'z' => {
+                    entry.push(c - b'a' + 1);
+                }
+                _ => {
+                    return Err(ParseError::new(
+                        line_num,
+                        line,
+                        line.get(pos..=pos).unwrap_or_default(),
+                        "a height from `a` to `z`, `S` or `E`",
+                    )
+                    .into());
+                }
+            }
+        }
+        grid.push(entry);
+    }
+    let Some(begin) = begin else {
+        return Err(ParseError::end_of_input(last.0, last.1, "a start `S`").into());
+    };
+    let Some(end) = end else {
+        return Err(ParseError::end_of_input(last.0, last.1, "an end `E`").into());
+    };
+    Ok(Input { grid, begin, end })
+}
+
+/// Fewest steps from S to E.
+///
+/// # Errors
+/// [`NoAnswer`] if there's no path.
+pub fn part1(map: &Input) -> Result<usize> {
+    // A* path from S -> E
+    let Some(res) = astar(
+        &map.begin,
+        |p| p.successors(&map.grid),
+        |p| p.distance(&map.end),
+        |p| *p == map.end,
+    ) else {
+        return Err(NoAnswer::new("there's no path from S to E").into());
+    };
+    Ok(res.0.len() - 1)
+}
+
+/// Fewest steps from any square at elevation a to E.
+///
+/// # Errors
+/// [`NoAnswer`] if there's no path from any of them.
+pub fn part2(map: &Input) -> Result<usize> {
+    let grid = &map.grid;
+    let mut best = Vec::new();
+    for y in 0..grid.len() {
+        for x in 0..grid[y].len() {
+            if grid[y][x] == 1 {
+                let res = astar(
+                    &Location(x, y),
+                    |p| p.successors(grid),
+                    |p| p.distance(&map.end),
+                    |p| *p == map.end,
+                );
+                if let Some(res) = res {
+                    best.push(res.0.len() - 1);
+                }
+            }
+        }
+    }
+    match best.iter().min() {
+        Some(b) => Ok(*b),
+        None => Err(NoAnswer::new("there's no path to E from any `a`").into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 31);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 29);
+        Ok(())
+    }
+
+    #[test]
+    fn no_path() -> Result<()> {
+        let map = parse("Sbz\nbcE\n")?;
+        assert!(part1(&map).unwrap_err().is::<NoAnswer>());
+        assert!(part2(&map).unwrap_err().is::<NoAnswer>());
+        assert!(parse("abc\n").unwrap_err().is::<ParseError>());
+        Ok(())
+    }
+}
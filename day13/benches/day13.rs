@@ -0,0 +1,2 @@
+//! day13 benchmarks.
+aoc_common::benches!(day13, [part1, part2]);
@@ -0,0 +1,225 @@
+//! day13 advent 2022
+use aoc_common::{lines, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use std::cmp::Ordering;
+use std::str;
+use strum_macros::{Display, EnumString};
+
+/// A packet value.
+#[derive(Clone, Debug, Display, EnumString)]
+pub enum Entry {
+    /// An integer.
+    Val(i32),
+    /// A list of values.
+    List(Vec<Entry>),
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare(self, other)
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+/// The packets in order, pairs are consecutive entries.
+pub type Input = Vec<Entry>;
+
+/// Parse the packets, skipping the blank lines between pairs.
+///
+/// # Errors
+/// If a packet isn't a well formed list.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut entries = Vec::new();
+    for (line_num, line) in lines(input) {
+        if line.is_empty() {
+            continue;
+        }
+
+        // Line always starts as a list
+        if !line.starts_with('[') {
+            return Err(
+                ParseError::new(line_num, line, line.get(..1).unwrap_or(line), "`[`").into(),
+            );
+        }
+        let mut c: usize = 1;
+        let r = parse_list(line_num, line, &mut c)?;
+        if let Some(rest) = line.get(c + 1..).filter(|r| !r.is_empty()) {
+            return Err(ParseError::new(line_num, line, rest, "end of line").into());
+        }
+        entries.push(r);
+    }
+    Ok(entries)
+}
+
+/// Sum of the indices of the pairs already in the right order.
+///
+/// # Errors
+/// [`NoAnswer`] if there's an odd number of packets.
+pub fn part1(entries: &Input) -> Result<usize> {
+    if entries.len() % 2 != 0 {
+        return Err(NoAnswer::new(format!("{} packets don't make pairs", entries.len())).into());
+    }
+    let mut pos = 0;
+    let mut good = Vec::new();
+    loop {
+        if pos >= entries.len() {
+            break;
+        }
+        if compare(&entries[pos], &entries[pos + 1]) == Ordering::Less {
+            good.push(pos / 2 + 1);
+        }
+        pos += 2;
+    }
+    Ok(good.iter().sum::<usize>())
+}
+
+/// Product of the indices of the divider packets once everything is sorted.
+///
+/// # Errors
+/// None, the dividers are always there to find.
+pub fn part2(entries: &Input) -> Result<usize> {
+    let mut entries2 = entries.clone();
+    let two = Entry::List(vec![Entry::Val(2)]);
+    let six = Entry::List(vec![Entry::Val(6)]);
+    entries2.push(Entry::List(vec![two.clone()]));
+    entries2.push(Entry::List(vec![six.clone()]));
+    entries2.sort();
+    let (mut ind1, mut ind2) = (0, 0);
+    for (i, e) in entries2.iter().enumerate() {
+        if e == &two {
+            ind1 = i + 1;
+        }
+        if e == &six {
+            ind2 = i + 1;
+        }
+    }
+    Ok(ind1 * ind2)
+}
+
+fn compare(entry1: &Entry, entry2: &Entry) -> Ordering {
+    match (entry1, entry2) {
+        (Entry::Val(a), Entry::Val(b)) => a.cmp(b),
+        (Entry::Val(_), Entry::List(_)) => compare(&Entry::List(vec![entry1.clone()]), entry2),
+        (Entry::List(_), Entry::Val(_)) => compare(entry1, &Entry::List(vec![entry2.clone()])),
+        (Entry::List(a), Entry::List(b)) => {
+            let mut aa = a.iter();
+            let mut bb = b.iter();
+
+            if a.is_empty() && b.is_empty() {
+                return Ordering::Equal;
+            }
+            loop {
+                let na = aa.next();
+                let nb = bb.next();
+                // If they both go empty together this is equality, not left running out first.
+                if na.is_none() && nb.is_none() {
+                    return Ordering::Equal;
+                }
+                // Left side running out is fine.
+                if na.is_none() {
+                    return Ordering::Less;
+                }
+                // 2nd one running out is bad.
+                if nb.is_none() {
+                    return Ordering::Greater;
+                }
+                let na = na.unwrap();
+                let nb = nb.unwrap();
+                if let (Entry::Val(compa), Entry::Val(compb)) = (na, nb) {
+                    if compa > compb {
+                        return Ordering::Greater;
+                    }
+                    if compa < compb {
+                        return Ordering::Less;
+                    }
+                } else {
+                    let ret = compare(na, nb);
+                    if ret != Ordering::Equal {
+                        return ret;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn parse_list(line_num: usize, line: &str, c: &mut usize) -> Result<Entry> {
+    let b = line.as_bytes();
+    let mut entry = Vec::new();
+    let mut start = None;
+    loop {
+        if *c >= b.len() {
+            return Err(ParseError::end_of_line(line_num, line, "`]`").into());
+        }
+        match b[*c] {
+            b'[' => {
+                *c += 1;
+                let e = parse_list(line_num, line, c)?;
+                entry.push(e);
+            }
+            // At the top level comma's are just skipped.
+            b',' | b']' => {
+                if let Some(s) = start {
+                    let val = ParseError::parse(line_num, line, &line[s..*c], "a number")?;
+                    entry.push(Entry::Val(val));
+                    start = None;
+                }
+                if b[*c] == b']' {
+                    break;
+                }
+            }
+            _ => {
+                if start.is_none() {
+                    start = Some(*c);
+                }
+            }
+        }
+        *c += 1;
+    }
+    Ok(Entry::List(entry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 13);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 140);
+        Ok(())
+    }
+
+    #[test]
+    fn odd_packets() -> Result<()> {
+        let entries = parse("[1]\n[2]\n\n[3]\n")?;
+        assert!(part1(&entries).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+
+    #[test]
+    fn bare_values() {
+        assert!(Entry::Val(1) < Entry::Val(2));
+        assert_eq!(Entry::Val(3), Entry::List(vec![Entry::Val(3)]));
+    }
+}
@@ -0,0 +1,2 @@
+//! day14 benchmarks.
+aoc_common::benches!(day14, [part1, part2]);
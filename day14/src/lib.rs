@@ -0,0 +1,267 @@
+//! day14 advent 2022
+use aoc_common::{lines, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use std::collections::HashMap;
+use strum_macros::Display;
+
+/// An x,y position in the cave. y grows downwards.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Location(pub i32, pub i32);
+
+#[derive(Clone, Debug, Display, PartialEq)]
+enum State {
+    Infinity,
+    Stopped,
+}
+
+/// What fills a location.
+#[derive(Clone, Debug, Display, PartialEq)]
+pub enum Type {
+    /// Part of the rock structure.
+    Rock,
+    /// Sand that has come to rest.
+    Sand,
+}
+
+/// The scanned rock.
+pub type Input = HashMap<Location, Type>;
+
+/// Parse the rock paths.
+///
+/// # Errors
+/// On a malformed path or a diagonal segment.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut hm = HashMap::new();
+    for (line_num, line) in lines(input) {
+        let parts = line.split(" -> ").collect::<Vec<_>>();
+        parse_line(&parts, &mut hm, line, line_num)?;
+    }
+    Ok(hm)
+}
+
+/// Units of sand at rest before sand falls into the abyss.
+///
+/// # Errors
+/// See [`pour`].
+pub fn part1(cave: &Input) -> Result<usize> {
+    pour(cave, false, false)
+}
+
+/// Units of sand at rest once the source is blocked with a floor.
+///
+/// # Errors
+/// [`NoAnswer`] if there's no rock to put the floor under.
+pub fn part2(cave: &Input) -> Result<usize> {
+    pour(cave, true, false)
+}
+
+/// Pour sand until it falls forever or the source is blocked and return
+/// how many units came to rest. `floor` adds the infinite floor 2 below
+/// the lowest rock. `draw` prints the board as each unit falls.
+///
+/// # Errors
+/// [`NoAnswer`] if there's a floor but no rock to put it under.
+pub fn pour(cave: &Input, floor: bool, draw: bool) -> Result<usize> {
+    if floor && cave.is_empty() {
+        return Err(
+            NoAnswer::new("the floor goes under the lowest rock but there isn't any").into(),
+        );
+    }
+    let mut hm = cave.clone();
+    let (minx, max_x, max_y) = extent(cave);
+    let bot = max_y + 2;
+    let mut sand = 0;
+    loop {
+        sand += 1;
+        let mut cur = Location(500, 0);
+        _ = draw && print_board(&hm, &cur, minx, max_x, max_y);
+        if hm.contains_key(&cur) {
+            break;
+        }
+
+        let state = loop {
+            if !floor && (cur.0 < minx || cur.0 > max_x || cur.1 > max_y) {
+                break State::Infinity;
+            }
+            cur = Location(cur.0, cur.1 + 1);
+            // Straight down ok, continue
+            if !hm.contains_key(&cur) {
+                if floor && cur.1 == bot {
+                    cur = Location(cur.0, cur.1 - 1);
+                    break State::Stopped;
+                }
+                _ = draw && print_board(&hm, &cur, minx, max_x, max_y);
+                continue;
+            }
+            // Try left
+            cur = Location(cur.0 - 1, cur.1);
+            if !hm.contains_key(&cur) {
+                _ = draw && print_board(&hm, &cur, minx, max_x, max_y);
+                continue;
+            }
+            // Try right - if not we're stuck.
+            cur = Location(cur.0 + 2, cur.1);
+            if hm.contains_key(&cur) {
+                cur = Location(cur.0 - 1, cur.1 - 1);
+                break State::Stopped;
+            }
+        };
+        if state == State::Infinity {
+            break;
+        }
+        _ = draw && print_board(&hm, &cur, minx, max_x, max_y);
+        hm.insert(cur, Type::Sand);
+    }
+    // Counted infinity particle
+    Ok(sand - 1)
+}
+
+/// The leftmost x, rightmost x and lowest y of the rock. With no rock the
+/// lows are `i32::MAX` and the highs `i32::MIN`.
+#[must_use]
+pub fn extent(cave: &Input) -> (i32, i32, i32) {
+    let mut max_x = i32::MIN;
+    let mut minx = i32::MAX;
+    let mut max_y = i32::MIN;
+    for l in cave.keys() {
+        if l.0 > max_x {
+            max_x = l.0;
+        }
+        if l.0 < minx {
+            minx = l.0;
+        }
+        if l.1 > max_y {
+            max_y = l.1;
+        }
+    }
+    (minx, max_x, max_y)
+}
+
+fn parse_line(
+    parts: &[&str],
+    hm: &mut HashMap<Location, Type>,
+    line: &str,
+    line_num: usize,
+) -> Result<()> {
+    let point = |p: &str| -> Result<(i32, i32)> {
+        let Some((x, y)) = p.split_once(',') else {
+            return Err(ParseError::new(line_num, line, p, "a point like `498,4`").into());
+        };
+        Ok((
+            ParseError::parse(line_num, line, x, "a number")?,
+            ParseError::parse(line_num, line, y, "a number")?,
+        ))
+    };
+    if parts.len() < 2 {
+        return Err(ParseError::end_of_line(line_num, line, "` -> `").into());
+    }
+    let (mut old_x, mut old_y) = point(parts[0])?;
+
+    for p in &parts[1..] {
+        let (x, y) = point(p)?;
+        match (x == old_x, y == old_y) {
+            (true, true) | (false, false) => {
+                return Err(ParseError::new(
+                    line_num,
+                    line,
+                    p,
+                    "a point in line with the one before",
+                )
+                .into());
+            }
+            (true, false) => {
+                let (l, h);
+                if old_y < y {
+                    (l, h) = (old_y, y);
+                } else {
+                    (l, h) = (y, old_y);
+                }
+                for d in l..=h {
+                    hm.insert(Location(x, d), Type::Rock);
+                }
+            }
+            (false, true) => {
+                let (l, h);
+                if old_x < x {
+                    (l, h) = (old_x, x);
+                } else {
+                    (l, h) = (x, old_x);
+                }
+                for d in l..=h {
+                    hm.insert(Location(d, y), Type::Rock);
+                }
+            }
+        }
+        old_x = x;
+        old_y = y;
+    }
+    Ok(())
+}
+
+fn print_board(
+    hm: &HashMap<Location, Type>,
+    cur: &Location,
+    minx: i32,
+    max_x: i32,
+    max_y: i32,
+) -> bool {
+    for y in 0..=max_y {
+        for x in 0..=max_x - minx {
+            let mut print;
+            let check = Location(x + minx, y);
+            if hm.contains_key(&check) {
+                match hm.get(&check).unwrap() {
+                    Type::Rock => {
+                        print = "#";
+                    }
+                    Type::Sand => {
+                        print = "o";
+                    }
+                }
+            } else {
+                print = ".";
+            }
+
+            if &check == cur {
+                print = "o";
+            }
+            print!("{print}");
+        }
+        println!();
+    }
+    println!();
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 24);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 93);
+        Ok(())
+    }
+
+    #[test]
+    fn example_extent() -> Result<()> {
+        assert_eq!(extent(&parse(EXAMPLE)?), (494, 503, 9));
+        Ok(())
+    }
+
+    #[test]
+    fn no_rock() -> Result<()> {
+        let cave = parse("")?;
+        assert_eq!(part1(&cave)?, 0);
+        assert!(part2(&cave).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+}
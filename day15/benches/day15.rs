@@ -0,0 +1,11 @@
+//! day15 benchmarks.
+// The puzzle's row and bounds mean nothing for the example and it's one
+// of the slow days.
+aoc_common::benches!(
+    day15,
+    [
+        part1 (example: |p| day15::covered(p, 10)),
+        part2 (example: |p| day15::tuning_frequency(p, 0, 20)),
+    ],
+    sample_size = 10
+);
@@ -0,0 +1,234 @@
+//! day15 advent 2022
+use aoc_common::{lines, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use std::collections::HashMap;
+
+/// An x,y position.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Ent(pub i64, pub i64);
+
+/// Row checked in part 1.
+pub const TARGET: i64 = 2_000_000;
+
+/// Largest coordinate the distress beacon can have in part 2.
+pub const BOUNDING: i64 = 4_000_000;
+
+/// The sensors and beacons.
+#[derive(Debug)]
+pub struct Input {
+    /// Sensors map to the distance to their closest beacon. Beacons map to 0.
+    pub inp: HashMap<Ent, u64>,
+    /// Smallest x any sensor covers.
+    pub min_x: i64,
+    /// Largest x any sensor covers.
+    pub max_x: i64,
+    /// Smallest y any sensor covers.
+    pub min_y: i64,
+    /// Largest y any sensor covers.
+    pub max_y: i64,
+}
+
+/// Parse the sensor report.
+///
+/// # Errors
+/// On a malformed line.
+pub fn parse(input: &str) -> Result<Input> {
+    let (mut min_x, mut max_x, mut min_y, mut max_y) = (i64::MAX, i64::MIN, i64::MAX, i64::MIN);
+    let mut inp = HashMap::new();
+    for (line_num, line) in lines(input) {
+        let parts = line.split_whitespace().collect::<Vec<_>>();
+        if parts.len() < 10 {
+            return Err(ParseError::end_of_line(line_num, line, "a beacon position").into());
+        }
+        if let Some(extra) = parts.get(10) {
+            return Err(ParseError::new(line_num, line, extra, "end of line").into());
+        }
+        // Each coordinate looks like `x=2,`.
+        let coord = |p: &str, prefix: &str| -> Result<i64> {
+            let Some(v) = p.strip_prefix(prefix) else {
+                return Err(ParseError::new(line_num, line, p, format!("`{prefix}`")).into());
+            };
+            Ok(ParseError::parse(
+                line_num,
+                line,
+                v.trim_end_matches([',', ':']),
+                "a coordinate",
+            )?)
+        };
+        let x = coord(parts[2], "x=")?;
+        let y = coord(parts[3], "y=")?;
+        let bx = coord(parts[8], "x=")?;
+        let by = coord(parts[9], "y=")?;
+        let dist = x.abs_diff(bx) + y.abs_diff(by);
+
+        min_check(x, &mut min_x, dist)?;
+        min_check(bx, &mut min_x, 0)?;
+        max_check(x, &mut max_x, dist)?;
+        max_check(bx, &mut max_x, 0)?;
+        min_check(y, &mut min_y, dist)?;
+        min_check(by, &mut min_y, 0)?;
+        max_check(y, &mut max_y, dist)?;
+        max_check(by, &mut max_y, 0)?;
+        inp.insert(Ent(x, y), dist);
+        inp.insert(Ent(bx, by), 0);
+    }
+    Ok(Input {
+        inp,
+        min_x,
+        max_x,
+        min_y,
+        max_y,
+    })
+}
+
+/// Positions on row 2000000 where a beacon can't be.
+///
+/// # Errors
+/// See [`covered`].
+pub fn part1(sensors: &Input) -> Result<i64> {
+    covered(sensors, TARGET)
+}
+
+/// Tuning frequency of the only position in 0..=4000000 a beacon can be.
+///
+/// # Errors
+/// [`NoAnswer`] if there isn't one.
+pub fn part2(sensors: &Input) -> Result<i64> {
+    tuning_frequency(sensors, 0, BOUNDING)
+}
+
+/// Positions on row `target` where a beacon can't be.
+///
+/// # Errors
+/// If a distance overflows.
+pub fn covered(sensors: &Input, target: i64) -> Result<i64> {
+    // Find all covered spaces on row target and then subtract off any beacons/sensors on that line.
+    Ok(cover2_row(&sensors.inp, target, sensors.min_x, true)?.unwrap_or_default())
+}
+
+/// Tuning frequency of the first position in the `min..=max` square no
+/// sensor covers.
+///
+/// # Errors
+/// [`NoAnswer`] if every position is covered.
+pub fn tuning_frequency(sensors: &Input, min: i64, max: i64) -> Result<i64> {
+    for y in min..=max {
+        if let Some(x) = cover2_row(&sensors.inp, y, min, false)? {
+            return Ok(x * 4_000_000 + y);
+        }
+    }
+    Err(NoAnswer::new(format!("no uncovered position in {min}..={max}")).into())
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+struct Interval(i64, i64);
+
+// With count set returns the number of covered positions on the row.
+// Otherwise returns the x of the first gap if there is one.
+fn cover2_row(
+    inp: &HashMap<Ent, u64>,
+    target: i64,
+    start_min: i64,
+    count: bool,
+) -> Result<Option<i64>> {
+    let mut ints = Vec::new();
+    let mut beacons = 0;
+    for (k, v) in inp {
+        // we now skip beacons.
+        if *v == 0 {
+            if k.1 == target {
+                beacons += 1;
+            }
+            continue;
+        }
+        let dist = k.1.abs_diff(target);
+        if dist <= *v {
+            let tot: i64 = ((*v * 2 + 1) - dist * 2).try_into()?;
+            let start = k.0 - (tot / 2);
+            ints.push(Interval(start, tot));
+        }
+    }
+    ints.sort();
+    let mut min = start_min;
+    let mut c = 0;
+    for i in ints {
+        // Skip if outside our box/overlap with an existing span.
+        if i.0 + i.1 < min {
+            continue;
+        }
+        if i.0 > min {
+            if count {
+                c += i.1 - i.0;
+            } else {
+                // First gap we can just abort.
+                if !inp.contains_key(&Ent(min, target)) {
+                    return Ok(Some(min));
+                }
+            }
+        } else if count {
+            c += i.1 - (min - i.0);
+        }
+
+        if i.0 < min {
+            min = (min - (min - i.0)) + i.1;
+        } else {
+            min += (i.0 - min) + (i.1 - i.0);
+        }
+    }
+    c -= beacons;
+    if count {
+        return Ok(Some(c));
+    }
+    Ok(None)
+}
+
+fn min_check(a: i64, min: &mut i64, dist: u64) -> Result<()> {
+    if a < *min {
+        *min = a;
+    }
+    let d: i64 = dist.try_into()?;
+    if a - d < *min {
+        *min = a - d;
+    }
+    Ok(())
+}
+
+fn max_check(a: i64, max: &mut i64, dist: u64) -> Result<()> {
+    if a > *max {
+        *max = a;
+    }
+    let d: i64 = dist.try_into()?;
+    if a + d > *max {
+        *max = a + d;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    // The example checks row 10 and a 0..=20 square instead.
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(covered(&parse(EXAMPLE)?, 10)?, 26);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(tuning_frequency(&parse(EXAMPLE)?, 0, 20)?, 56_000_011);
+        Ok(())
+    }
+
+    #[test]
+    fn fully_covered() -> Result<()> {
+        let sensors = parse("Sensor at x=5, y=5: closest beacon is at x=15, y=5\n")?;
+        assert!(tuning_frequency(&sensors, 0, 10)
+            .unwrap_err()
+            .is::<NoAnswer>());
+        Ok(())
+    }
+}
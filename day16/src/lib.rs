@@ -0,0 +1,836 @@
+//! day16 advent 2022
+use aoc_common::{lines, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// A valve and the tunnels leading from it.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Location {
+    /// Flow rate once opened.
+    pub flow: usize,
+    /// Valves reachable in one minute.
+    pub neighbors: Vec<String>,
+}
+
+/// The valves by name.
+pub type Input = HashMap<String, Location>;
+
+/// Parse the valve scan.
+///
+/// # Errors
+/// On a malformed line.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut hm = HashMap::new();
+    for (line_num, line) in lines(input) {
+        let parts = line.split_whitespace().collect::<Vec<_>>();
+        if parts.len() < 10 {
+            return Err(ParseError::end_of_line(line_num, line, "a list of tunnels").into());
+        }
+
+        let Some(rate) = parts[4]
+            .strip_prefix("rate=")
+            .and_then(|r| r.strip_suffix(';'))
+        else {
+            return Err(ParseError::new(line_num, line, parts[4], "`rate=N;`").into());
+        };
+        let rate = ParseError::parse(line_num, line, rate, "a flow rate")?;
+        let mut n = Vec::new();
+        for p in &parts[9..] {
+            n.push(String::from(p.trim_end_matches(',')));
+        }
+        hm.insert(
+            String::from(parts[1]),
+            Location {
+                flow: rate,
+                neighbors: n,
+            },
+        );
+    }
+    Ok(hm)
+}
+
+// Every tunnel has to lead somewhere and there has to be a valve to start at.
+fn check(hm: &Input, start: &str) -> Result<()> {
+    if !hm.contains_key(start) {
+        return Err(NoAnswer::new(format!("there's no valve {start} to start at")).into());
+    }
+    for (k, v) in hm {
+        if let Some(n) = v.neighbors.iter().find(|n| !hm.contains_key(*n)) {
+            return Err(NoAnswer::new(format!("valve {k} leads to unknown valve {n}")).into());
+        }
+    }
+    Ok(())
+}
+
+/// The valves worth opening, numbered so a set of them fits in a bitmask,
+/// and the minutes it takes to walk between them.
+#[derive(Clone, Debug)]
+pub struct Network {
+    /// Names of the valves with flow in name order, then the start valve
+    /// if it has none.
+    pub names: Vec<String>,
+    /// Flow rate of each valve.
+    pub flows: Vec<usize>,
+    /// Index of the start valve.
+    pub start: usize,
+    /// `dist[a][b]` is the minutes it takes to walk from `a` to `b`, `None`
+    /// if there's no way there.
+    pub dist: Vec<Vec<Option<usize>>>,
+    // Every valve by name, the tunnels from each and the minutes between
+    // them, for retracing a walk step by step.
+    all: Vec<String>,
+    tunnels: Vec<Vec<usize>>,
+    steps: Vec<Vec<Option<usize>>>,
+    // Where each valve in `names` is in `all`.
+    full: Vec<usize>,
+}
+
+impl Network {
+    /// Number the valves with flow and find the shortest walks between them
+    /// and the `start` valve.
+    ///
+    /// # Errors
+    /// [`NoAnswer`] if there's no valve `start`, a tunnel leads to a valve
+    /// that doesn't exist or more than 64 valves have flow.
+    pub fn new(hm: &Input, start: &str) -> Result<Self> {
+        check(hm, start)?;
+        // Floyd–Warshall over every valve.
+        let all = hm.keys().sorted().collect::<Vec<_>>();
+        let index = all
+            .iter()
+            .enumerate()
+            .map(|(i, k)| (*k, i))
+            .collect::<HashMap<_, _>>();
+        let mut dist = vec![vec![None; all.len()]; all.len()];
+        for (i, k) in all.iter().enumerate() {
+            dist[i][i] = Some(0);
+            for n in &hm[*k].neighbors {
+                dist[i][index[n]] = Some(1);
+            }
+        }
+        for k in 0..all.len() {
+            let from_k = dist[k].clone();
+            for row in &mut dist {
+                let Some(ik) = row[k] else {
+                    continue;
+                };
+                for (ij, kj) in row.iter_mut().zip(&from_k) {
+                    if let Some(kj) = kj {
+                        if ij.map_or(true, |ij| ik + kj < ij) {
+                            *ij = Some(ik + kj);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut keep = (0..all.len())
+            .filter(|i| hm[all[*i]].flow != 0)
+            .collect::<Vec<_>>();
+        if keep.len() > 64 {
+            return Err(NoAnswer::new(format!(
+                "{} valves have flow, can only keep track of 64",
+                keep.len()
+            ))
+            .into());
+        }
+        let home = index[&String::from(start)];
+        if !keep.contains(&home) {
+            keep.push(home);
+        }
+        Ok(Self {
+            names: keep.iter().map(|i| all[*i].clone()).collect(),
+            flows: keep.iter().map(|i| hm[all[*i]].flow).collect(),
+            start: keep.iter().position(|i| *i == home).unwrap_or_default(),
+            dist: keep
+                .iter()
+                .map(|a| keep.iter().map(|b| dist[*a][*b]).collect())
+                .collect(),
+            tunnels: all
+                .iter()
+                .map(|k| hm[*k].neighbors.iter().map(|n| index[n]).collect())
+                .collect(),
+            all: all.into_iter().cloned().collect(),
+            steps: dist,
+            full: keep,
+        })
+    }
+
+    // The valves passed through walking from `a` to `b`, ending with `b`.
+    // Where there's a choice the tunnel listed first is taken.
+    fn walk(&self, a: usize, b: usize) -> Vec<&str> {
+        let (mut at, to) = (self.full[a], self.full[b]);
+        let mut walk = Vec::new();
+        while let Some(left @ 1..) = self.steps[at][to] {
+            let Some(next) = self.tunnels[at]
+                .iter()
+                .find(|n| self.steps[**n][to] == Some(left - 1))
+            else {
+                break;
+            };
+            at = *next;
+            walk.push(self.all[at].as_str());
+        }
+        walk
+    }
+
+    // The start followed by the valves opened, by name.
+    fn named(&self, route: &[usize]) -> Vec<String> {
+        std::iter::once(&self.start)
+            .chain(route)
+            .map(|v| self.names[*v].clone())
+            .collect()
+    }
+}
+
+// The memo is hit hundreds of thousands of times with keys that are just a
+// few small numbers, which SipHash is needlessly slow for. Multiply and
+// rotate, as rustc's FxHash does.
+#[derive(Default)]
+struct KeyHasher(u64);
+
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.write_u64(u64::from(*b));
+        }
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 = (self.0.rotate_left(5) ^ n).wrapping_mul(0x517c_c1b7_2722_0a95);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+}
+
+// The most pressure that can be released from a position, memoized on
+// (valve, minutes left, valves that are open or not ours to open).
+struct Search<'a> {
+    net: &'a Network,
+    memo: HashMap<(usize, usize, u64), usize, BuildHasherDefault<KeyHasher>>,
+}
+
+impl<'a> Search<'a> {
+    fn new(net: &'a Network) -> Self {
+        Self {
+            net,
+            memo: HashMap::default(),
+        }
+    }
+
+    // Valves that can still be opened from `at` in time to help, with the
+    // minutes left once each is open.
+    fn moves(
+        &self,
+        at: usize,
+        left: usize,
+        done: u64,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let net = self.net;
+        (0..net.flows.len()).filter_map(move |v| {
+            if net.flows[v] == 0 || done & (1 << v) != 0 {
+                return None;
+            }
+            // Walk there and a minute to open it.
+            let cost = net.dist[at][v]? + 1;
+            (cost < left).then(|| (v, left - cost))
+        })
+    }
+
+    fn best(&mut self, at: usize, left: usize, done: u64) -> usize {
+        if let Some(b) = self.memo.get(&(at, left, done)) {
+            return *b;
+        }
+        let mut best = 0;
+        for (v, rest) in self.moves(at, left, done) {
+            best = best.max(self.net.flows[v] * rest + self.best(v, rest, done | 1 << v));
+        }
+        self.memo.insert((at, left, done), best);
+        best
+    }
+
+    // The most pressure one agent starting at `at` can release from each
+    // set of valves, indexed by bitmask.
+    fn alone(&self, at: usize, left: usize) -> Vec<usize> {
+        let valves = self.net.flows.iter().filter(|f| **f != 0).count();
+        let mut best = vec![0; 1 << valves];
+        self.walk(at, left, 0, 0, &mut best);
+        // That's only routes opening exactly each set. Whatever can be done
+        // with a set can be done with any set containing it too.
+        for bit in 0..valves {
+            for set in 0..best.len() {
+                if set & 1 << bit != 0 {
+                    best[set] = best[set].max(best[set ^ 1 << bit]);
+                }
+            }
+        }
+        best
+    }
+
+    // Every route on from `at` having opened `set` and released `released`.
+    fn walk(&self, at: usize, left: usize, set: usize, released: usize, best: &mut [usize]) {
+        best[set] = best[set].max(released);
+        for (v, rest) in self.moves(at, left, set as u64) {
+            self.walk(
+                v,
+                rest,
+                set | 1 << v,
+                released + self.net.flows[v] * rest,
+                best,
+            );
+        }
+    }
+
+    // The valves opened, in order, to get `best(at, left, done)`.
+    fn route(&mut self, mut at: usize, mut left: usize, mut done: u64) -> Vec<usize> {
+        let mut route = Vec::new();
+        loop {
+            let want = self.best(at, left, done);
+            let next = self
+                .moves(at, left, done)
+                .collect::<Vec<_>>()
+                .into_iter()
+                .find(|(v, rest)| {
+                    self.net.flows[*v] * rest + self.best(*v, *rest, done | 1 << v) == want
+                });
+            let Some((v, rest)) = next else {
+                return route;
+            };
+            route.push(v);
+            (at, left, done) = (v, rest, done | 1 << v);
+        }
+    }
+}
+
+/// Who's opening valves, where they start and for how long.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Team<'a> {
+    /// The valve everyone starts at.
+    pub start: &'a str,
+    /// How many walk around opening valves together.
+    pub agents: usize,
+    /// Minutes until the volcano erupts.
+    pub minutes: usize,
+    /// Minutes spent teaching the others before anyone sets off.
+    pub training: usize,
+}
+
+impl Team<'static> {
+    /// Just you, for part 1.
+    pub const SOLO: Self = Self {
+        start: "AA",
+        agents: 1,
+        minutes: 30,
+        training: 0,
+    };
+
+    /// You and an elephant you spend 4 minutes teaching, for part 2.
+    pub const WITH_ELEPHANT: Self = Self {
+        start: "AA",
+        agents: 2,
+        minutes: 30,
+        training: 4,
+    };
+}
+
+// Most valves that can be shared out between two agents. Every set of them
+// gets an entry in a table and the split of the whole set is picked from
+// 2^20 ways.
+const MAX_SHARED: usize = 20;
+
+// Most valves that can be shared out between three or more agents. Every
+// agent but the last tries each split of each set, 3^n steps apiece, which
+// is 3.5e9 at 20 valves but only 1.4e7 at 15.
+const MAX_SHARED_MANY: usize = 15;
+
+/// Most pressure one person can release in 30 minutes.
+///
+/// # Errors
+/// [`NoAnswer`] if there's no valve AA, a tunnel leads to a valve that
+/// doesn't exist or more than 64 valves have flow.
+pub fn part1(hm: &Input) -> Result<usize> {
+    Ok(plan(hm, Team::SOLO)?.0)
+}
+
+/// Most pressure you and an elephant can release in 26 minutes.
+///
+/// # Errors
+/// Same as [`plan`].
+pub fn part2(hm: &Input) -> Result<usize> {
+    Ok(plan(hm, Team::WITH_ELEPHANT)?.0)
+}
+
+/// Most pressure `team` can release and each agent's route: the start
+/// valve then the valves they open in order.
+///
+/// Each valve is opened by at most one agent, so this tries every way of
+/// sharing the valves out, giving each agent the best it can do with its
+/// share.
+///
+/// # Errors
+/// [`NoAnswer`] if there's no valve to start at, a tunnel leads to a valve
+/// that doesn't exist, the team has no one in it, more than 64 valves have flow
+/// or they're shared between 2 agents and more than 20 have flow, or between
+/// more agents and more than 15 do.
+pub fn plan(hm: &Input, team: Team) -> Result<(usize, Vec<Vec<String>>)> {
+    let net = Network::new(hm, team.start)?;
+    let (total, routes) = solve(&net, team)?;
+    Ok((total, routes.iter().map(|r| net.named(r)).collect()))
+}
+
+/// The puzzle's minute by minute account of `team` following `routes`, as
+/// [`plan`] returns them: the valves open and the pressure they release,
+/// and where each agent moves or which valve it opens.
+///
+/// # Errors
+/// [`NoAnswer`] if there's no valve to start at, a tunnel leads to a valve
+/// that doesn't exist, more than 64 valves have flow or a route doesn't
+/// start at the start valve, names a valve without flow or one that can't
+/// be reached, or a valve is opened more than once.
+pub fn explain(hm: &Input, team: Team, routes: &[Vec<String>]) -> Result<String> {
+    let net = Network::new(hm, team.start)?;
+    let index = net
+        .names
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.as_str(), i))
+        .collect::<HashMap<_, _>>();
+    let mut valves = Vec::new();
+    // Across every agent, each valve only opens once.
+    let mut opened = 0_u64;
+    for route in routes {
+        let Some((_, rest)) = route
+            .split_first()
+            .filter(|(first, _)| *first == team.start)
+        else {
+            return Err(NoAnswer::new(format!("a route doesn't start at {}", team.start)).into());
+        };
+        let mut at = net.start;
+        let mut route = Vec::new();
+        for name in rest {
+            let v = index.get(name.as_str()).copied();
+            // With 64 valves that have flow the start valve doesn't fit in
+            // the mask.
+            let bit = v
+                .and_then(|v| u32::try_from(v).ok())
+                .and_then(|v| 1_u64.checked_shl(v));
+            match (v, bit) {
+                (Some(_), Some(bit)) if opened & bit != 0 => {
+                    return Err(NoAnswer::new(format!("{name} is opened more than once")).into());
+                }
+                (Some(v), Some(bit)) if net.flows[v] != 0 && net.dist[at][v].is_some() => {
+                    opened |= bit;
+                    route.push(v);
+                    at = v;
+                }
+                _ => return Err(NoAnswer::new(format!("{name} isn't a valve to open next")).into()),
+            }
+        }
+        valves.push(route);
+    }
+    let routes = valves;
+    let left = team.minutes.saturating_sub(team.training);
+
+    // What each agent does minute by minute.
+    let mut doing = Vec::new();
+    for (agent, route) in routes.iter().enumerate() {
+        let (you, s) = match (agent, routes.len()) {
+            (0, _) => (String::from("You"), ""),
+            (_, 2) => (String::from("The elephant"), "s"),
+            _ => (format!("Elephant {agent}"), "s"),
+        };
+        let mut acts = Vec::new();
+        let mut at = net.start;
+        for v in route {
+            for step in net.walk(at, *v) {
+                acts.push((format!("{you} move{s} to valve {step}."), None));
+            }
+            acts.push((format!("{you} open{s} valve {}.", net.names[*v]), Some(*v)));
+            at = *v;
+        }
+        doing.push(acts);
+    }
+
+    let mut open = Vec::<usize>::new();
+    let mut minutes = Vec::new();
+    for minute in 1..=left {
+        let mut lines = vec![format!("== Minute {minute} ==")];
+        let names = open
+            .iter()
+            .map(|v| &net.names[*v])
+            .sorted()
+            .collect::<Vec<_>>();
+        let releasing = open.iter().map(|v| net.flows[*v]).sum::<usize>();
+        lines.push(match names.as_slice() {
+            [] => String::from("No valves are open."),
+            [v] => format!("Valve {v} is open, releasing {releasing} pressure."),
+            [a, b] => format!("Valves {a} and {b} are open, releasing {releasing} pressure."),
+            [most @ .., last] => format!(
+                "Valves {}, and {last} are open, releasing {releasing} pressure.",
+                most.iter().join(", ")
+            ),
+        });
+        for acts in &doing {
+            if let Some((act, opened)) = acts.get(minute - 1) {
+                lines.push(act.clone());
+                // Only releases from the next minute.
+                open.extend(opened);
+            }
+        }
+        minutes.push(lines.join("\n"));
+    }
+    Ok(minutes.join("\n\n") + "\n")
+}
+
+// The most pressure `team` can release in `net` and the valves each agent
+// opens in order.
+fn solve(net: &Network, team: Team) -> Result<(usize, Vec<Vec<usize>>)> {
+    let left = team.minutes.saturating_sub(team.training);
+    let mut search = Search::new(net);
+    let valves = net.flows.iter().filter(|f| **f != 0).count();
+    let sets = match team.agents {
+        0 => return Err(NoAnswer::new("there's no one to open the valves").into()),
+        1 => vec![u64::MAX],
+        k => {
+            let max = if k > 2 { MAX_SHARED_MANY } else { MAX_SHARED };
+            if valves > max {
+                return Err(NoAnswer::new(format!(
+                    "{valves} valves have flow, can only share {max} between {k} agents"
+                ))
+                .into());
+            }
+            share(&search.alone(net.start, left), k, valves)
+        }
+    };
+
+    let mut total = 0;
+    let mut routes = Vec::new();
+    for set in sets {
+        total += search.best(net.start, left, !set);
+        routes.push(search.route(net.start, left, !set));
+    }
+    Ok((total, routes))
+}
+
+// Split the `valves` between `k` agents to release the most pressure, given
+// the most one agent can release from each set of them. Returns each
+// agent's set.
+fn share(alone: &[usize], k: usize, valves: usize) -> Vec<u64> {
+    let all = (1 << valves) - 1;
+    // best[mask] is the most j agents release between them from the valves
+    // in mask, pick[j][mask] the set the j'th of them gets. One agent gets
+    // the lot.
+    let mut best = alone.to_vec();
+    let mut pick = vec![(0..=all).collect::<Vec<_>>()];
+    for j in 2..=k {
+        // Only the whole set matters for the last agent.
+        let masks = if j == k { all..=all } else { 0..=all };
+        let mut more = vec![0; all + 1];
+        let mut picked = vec![0; all + 1];
+        for mask in masks {
+            // Every subset of mask, this agent taking it and the others the
+            // rest.
+            let mut sub = mask;
+            loop {
+                let total = alone[sub] + best[mask ^ sub];
+                if total > more[mask] {
+                    (more[mask], picked[mask]) = (total, sub);
+                }
+                if sub == 0 {
+                    break;
+                }
+                sub = (sub - 1) & mask;
+            }
+        }
+        best = more;
+        pick.push(picked);
+    }
+
+    let mut mask = all;
+    let mut sets = Vec::new();
+    for picked in pick.iter().rev() {
+        sets.push(picked[mask] as u64);
+        mask ^= picked[mask];
+    }
+    sets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 1651);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 1707);
+        Ok(())
+    }
+
+    #[test]
+    fn uneven_split() -> Result<()> {
+        // Three small valves close by and a big one far off. One of you
+        // should open the small ones and the other the big one.
+        let valves = parse(
+            "\
+Valve AA has flow rate=0; tunnels lead to valves BB, XA
+Valve BB has flow rate=5; tunnels lead to valves AA, CC
+Valve CC has flow rate=5; tunnels lead to valves BB, DD
+Valve DD has flow rate=5; tunnel leads to valve CC
+Valve XA has flow rate=0; tunnels lead to valves AA, XB
+Valve XB has flow rate=0; tunnels lead to valves XA, XC
+Valve XC has flow rate=0; tunnels lead to valves XB, XD
+Valve XD has flow rate=0; tunnels lead to valves XC, ZZ
+Valve ZZ has flow rate=100; tunnel leads to valve XD
+",
+        )?;
+        let (best, routes) = plan(&valves, Team::WITH_ELEPHANT)?;
+        assert_eq!(best, 2330);
+        assert_eq!(routes, [vec!["AA", "ZZ"], vec!["AA", "BB", "CC", "DD"]]);
+
+        // More agents help with the small valves until there's one each.
+        for (agents, best) in [(3, 2340), (4, 2345), (5, 2345)] {
+            let team = Team {
+                agents,
+                ..Team::WITH_ELEPHANT
+            };
+            let (total, routes) = plan(&valves, team)?;
+            assert_eq!(total, best);
+            assert_eq!(routes.len(), agents);
+        }
+        let five = Team {
+            agents: 5,
+            ..Team::WITH_ELEPHANT
+        };
+        let idle = plan(&valves, five)?
+            .1
+            .iter()
+            .filter(|r| r.len() == 1)
+            .count();
+        assert_eq!(idle, 1);
+
+        // Too many valves to share between 3.
+        let names = (b'A'..=b'P')
+            .map(|c| format!("B{}", c as char))
+            .collect::<Vec<_>>();
+        let mut many = vec![format!(
+            "Valve AA has flow rate=0; tunnels lead to valves {}",
+            names.join(", ")
+        )];
+        for n in &names {
+            many.push(format!(
+                "Valve {n} has flow rate=1; tunnel leads to valve AA"
+            ));
+        }
+        let many = parse(&many.join("\n"))?;
+        let three = Team {
+            agents: 3,
+            minutes: 3,
+            training: 0,
+            ..Team::WITH_ELEPHANT
+        };
+        assert!(plan(&many, three).unwrap_err().is::<NoAnswer>());
+        let two = Team { agents: 2, ..three };
+        assert_eq!(plan(&many, two)?.0, 2);
+
+        // Training takes the time off everyone.
+        let solo = Team {
+            training: 4,
+            ..Team::SOLO
+        };
+        assert_eq!(plan(&valves, solo)?.0, 2000 + 5 * (13 + 11 + 9));
+        let nobody = Team {
+            agents: 0,
+            ..Team::SOLO
+        };
+        assert!(plan(&valves, nobody).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+
+    // Adds up the pressure released each minute of an explanation.
+    fn released(explained: &str) -> usize {
+        explained
+            .lines()
+            .filter_map(|l| l.split("releasing ").nth(1))
+            .map(|r| r.trim_end_matches(" pressure.").parse::<usize>().unwrap())
+            .sum()
+    }
+
+    #[test]
+    fn explain_example() -> Result<()> {
+        let valves = parse(EXAMPLE)?;
+        let solo = explain(&valves, Team::SOLO, &plan(&valves, Team::SOLO)?.1)?;
+        assert!(solo.starts_with(
+            "\
+== Minute 1 ==
+No valves are open.
+You move to valve DD.
+
+== Minute 2 ==
+No valves are open.
+You open valve DD.
+
+== Minute 3 ==
+Valve DD is open, releasing 20 pressure.
+You move to valve CC.
+
+== Minute 4 ==
+Valve DD is open, releasing 20 pressure.
+You move to valve BB.
+
+== Minute 5 ==
+Valve DD is open, releasing 20 pressure.
+You open valve BB.
+
+== Minute 6 ==
+Valves BB and DD are open, releasing 33 pressure.
+You move to valve AA.
+"
+        ));
+        assert!(solo.ends_with(
+            "\
+== Minute 30 ==
+Valves BB, CC, DD, EE, HH, and JJ are open, releasing 81 pressure.
+"
+        ));
+        assert_eq!(released(&solo), 1651);
+
+        let pair = explain(
+            &valves,
+            Team::WITH_ELEPHANT,
+            &plan(&valves, Team::WITH_ELEPHANT)?.1,
+        )?;
+        assert!(pair.starts_with(
+            "\
+== Minute 1 ==
+No valves are open.
+You move to valve II.
+The elephant moves to valve DD.
+
+== Minute 2 ==
+No valves are open.
+You move to valve JJ.
+The elephant opens valve DD.
+
+== Minute 3 ==
+Valve DD is open, releasing 20 pressure.
+You open valve JJ.
+The elephant moves to valve EE.
+"
+        ));
+        assert!(pair.contains("== Minute 26 ==\n"));
+        assert!(!pair.contains("== Minute 27 =="));
+        assert_eq!(released(&pair), 1707);
+
+        let routes = |r: &[&str]| vec![r.iter().map(ToString::to_string).collect::<Vec<_>>()];
+        assert!(explain(&valves, Team::SOLO, &routes(&["BB", "DD"]))
+            .unwrap_err()
+            .is::<NoAnswer>());
+        assert!(explain(&valves, Team::SOLO, &routes(&["AA", "CC", "II"]))
+            .unwrap_err()
+            .is::<NoAnswer>());
+        assert!(explain(&valves, Team::SOLO, &routes(&["AA", "DD", "DD"]))
+            .unwrap_err()
+            .is::<NoAnswer>());
+        let twice = [routes(&["AA", "DD"]), routes(&["AA", "BB", "DD"])].concat();
+        assert!(explain(&valves, Team::WITH_ELEPHANT, &twice)
+            .unwrap_err()
+            .is::<NoAnswer>());
+
+        // 64 valves with flow puts the start valve past the end of the mask.
+        let names = (b'B'..=b'D')
+            .flat_map(|a| (b'A'..=b'Z').map(move |b| format!("{}{}", a as char, b as char)))
+            .take(64)
+            .collect::<Vec<_>>();
+        let mut full = vec![format!(
+            "Valve AA has flow rate=0; tunnels lead to valves {}",
+            names.join(", ")
+        )];
+        for n in &names {
+            full.push(format!(
+                "Valve {n} has flow rate=1; tunnel leads to valve AA"
+            ));
+        }
+        let full = parse(&full.join("\n"))?;
+        assert!(explain(&full, Team::SOLO, &routes(&["AA", "AA"]))
+            .unwrap_err()
+            .is::<NoAnswer>());
+        assert!(explain(&full, Team::SOLO, &routes(&["AA", "DL"])).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn elsewhere() -> Result<()> {
+        let valves = parse(EXAMPLE)?;
+        // DD has flow itself, opening it takes the first minute.
+        let short = Team {
+            start: "DD",
+            minutes: 2,
+            ..Team::SOLO
+        };
+        let (best, routes) = plan(&valves, short)?;
+        assert_eq!(best, 20);
+        assert_eq!(routes, [["DD", "DD"]]);
+        let (best, routes) = plan(
+            &valves,
+            Team {
+                minutes: 20,
+                ..short
+            },
+        )?;
+        assert_eq!(best, 928);
+        assert_eq!(routes[0][..2], ["DD", "DD"]);
+        let nowhere = Team {
+            start: "ZZ",
+            ..Team::SOLO
+        };
+        assert!(plan(&valves, nowhere).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+
+    #[test]
+    fn network() -> Result<()> {
+        let valves = parse(EXAMPLE)?;
+        let net = Network::new(&valves, "AA")?;
+        assert_eq!(net.names, ["BB", "CC", "DD", "EE", "HH", "JJ", "AA"]);
+        assert_eq!(net.start, 6);
+        let walk = |a: &str, b: &str| {
+            let i = |n| net.names.iter().position(|v| v == n).unwrap();
+            net.dist[i(a)][i(b)]
+        };
+        assert_eq!(walk("AA", "JJ"), Some(2));
+        assert_eq!(walk("HH", "JJ"), Some(7));
+        assert_eq!(walk("CC", "CC"), Some(0));
+
+        let (best, routes) = plan(&valves, Team::SOLO)?;
+        assert_eq!(best, 1651);
+        assert_eq!(routes, [["AA", "DD", "BB", "JJ", "HH", "EE", "CC"]]);
+        let (best, routes) = plan(&valves, Team::WITH_ELEPHANT)?;
+        assert_eq!(best, 1707);
+        assert_eq!(routes, [["AA", "JJ", "BB", "CC"], ["AA", "DD", "HH", "EE"]]);
+        Ok(())
+    }
+
+    #[test]
+    fn missing_valves() -> Result<()> {
+        let no_start = parse("Valve BB has flow rate=13; tunnels lead to valves CC, BB\n")?;
+        assert!(part1(&no_start).unwrap_err().is::<NoAnswer>());
+        let dead_end = parse("Valve AA has flow rate=0; tunnels lead to valves BB, ZZ\n")?;
+        assert!(part1(&dead_end).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+}
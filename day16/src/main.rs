@@ -1,299 +1,75 @@
 //! day16 advent 2022
 use clap::Parser;
 use color_eyre::eyre::Result;
-use itertools::Itertools;
-use std::collections::{HashMap, VecDeque};
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
-use std::path::Path;
-use std::time::Instant;
+use day16::{explain, parse, plan, Team};
 
 #[derive(Parser)]
 #[command(author, version, about)]
 struct Args {
-    #[arg(long, default_value_t = String::from("input.txt"))]
-    filename: String,
+    #[command(flatten)]
+    common: aoc_common::Args,
 
-    #[arg(long, default_value_t = false)]
-    debug: bool,
-}
-
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
-struct Location {
-    flow: usize,
-    neighbors: Vec<String>,
-}
-
-fn main() -> Result<()> {
-    color_eyre::install()?;
-    let args: Args = Args::parse();
-
-    let filename = Path::new(env!("CARGO_MANIFEST_DIR")).join(args.filename);
-    let file = File::open(filename)?;
-    let lines: Vec<String> = io::BufReader::new(file).lines().flatten().collect();
-
-    let hm = parse_lines(&lines)?;
-    if args.debug {
-        for (k, v) in &hm {
-            println!("{k} - {v:?}");
-        }
-    }
-    let perms = hm
-        .keys()
-        .filter(|k| *k == "AA" || hm[*k].flow != 0)
-        .permutations(2)
-        .collect::<Vec<_>>();
-    if args.debug {
-        println!("perms");
-    }
-    let mut paths = HashMap::new();
-    for p in &perms {
-        let key = p[0].clone() + "->" + p[1];
-        paths.insert(key.clone(), find_path(&hm, p[0], p[1]));
-        if args.debug {
-            println!("{key} - {:?}", paths[&key]);
-        }
-    }
-    let nodes = hm
-        .iter()
-        .filter(|(_, v)| v.flow != 0)
-        .collect::<HashMap<_, _>>();
-    if args.debug {
-        println!("nodes - {nodes:?}");
-    }
-
-    let mut best = Vec::new();
-    let mut max = 0;
-    if nodes.len() <= 7 {
-        let mut c: u64 = 0;
-        for x in nodes.keys().permutations(nodes.len()) {
-            c += 1;
-            if args.debug && c % 1_000_000 == 0 {
-                println!("{c}");
-            }
-
-            let mut cur = String::from("AA");
-            let mut minutes = 0;
-            let mut flow_rate = 0;
-            let mut total_flow = 0;
-            for p in &x {
-                let key = cur.clone() + "->" + p;
-                let steps = paths[&key].len();
-                let new_flow_rate = hm[paths[&key][steps - 1]].flow;
-                total_flow += steps * flow_rate;
-                flow_rate += new_flow_rate;
-
-                minutes += steps;
-                cur = (**p).clone();
-            }
-            if minutes <= 30 {
-                let left = 30 - minutes;
-                total_flow += left * flow_rate;
-
-                if total_flow > max {
-                    max = total_flow;
-                    best = x.clone();
-                }
-            }
-        }
-        println!("part1 - AA -> {best:?} {max}");
-    }
-    let cur = String::from("AA");
-    let flows = nodes.keys().copied().collect::<Vec<_>>();
-    let now = Instant::now();
-    let ret = find_best(&cur, &flows, &nodes, &paths, 30);
-    let elapsed = Instant::now().duration_since(now);
-    println!("part1 - {elapsed:?} AA -> {:?} {}", ret.1, ret.0);
-
-    // Generate all P(flows.len(), flows.len()/2) perms and then for each one of those run again on the remainder
-    // NOTE: We can't just generate them all with itertools.permutate() since many paths have early ends (see test
-    // in find_best/find_best2). So those have to expand the permutations manually and then prune whole subpaths that
-    // can't work. This drastically reduces the search space to something which takes 5s to run.
-    let mut v = Vec::<String>::new();
-    let now = Instant::now();
-    let new_time = find_best2(
-        &cur,
-        &flows,
-        &nodes,
-        &paths,
-        26,
-        0,
-        &mut v,
-        0,
-        flows.len() / 2,
-    );
-    let elapsed = Instant::now().duration_since(now);
-    println!(
-        "part2 - {elapsed:?} -> from {:?} {}",
-        new_time.1, new_time.0
-    );
+    /// Valve everyone starts at.
+    #[arg(long, default_value_t = String::from(Team::SOLO.start))]
+    start: String,
 
-    Ok(())
-}
-
-fn parse_lines(lines: &[String]) -> Result<HashMap<String, Location>> {
-    let mut hm = HashMap::new();
-    for (line_num, line) in lines.iter().enumerate() {
-        let parts = line.split_whitespace().collect::<Vec<_>>();
-        assert!(parts.len() >= 10, "{} - bad line {line}", line_num + 1);
-
-        let rate = parts[4]
-            .strip_prefix("rate=")
-            .unwrap()
-            .strip_suffix(';')
-            .unwrap()
-            .parse::<usize>()?;
-        let mut n = Vec::new();
-        for p in &parts[9..] {
-            n.push(String::from(p.trim_end_matches(',')));
-        }
-        hm.insert(
-            String::from(parts[1]),
-            Location {
-                flow: rate,
-                neighbors: n,
-            },
-        );
-    }
-    Ok(hm)
-}
-
-fn find_best(
-    cur: &str,
-    flows: &[&String],
-    nodes: &HashMap<&String, &Location>,
-    paths: &HashMap<String, Vec<&String>>,
+    /// Minutes until the volcano erupts.
+    #[arg(long, default_value_t = Team::SOLO.minutes)]
     minutes: usize,
-) -> (usize, Vec<String>) {
-    let mut choices = Vec::new();
-
-    for f in flows.iter().copied() {
-        let key = format!("{cur}->{f}");
-        let steps = paths[&key].len();
 
-        // Prune off branches that can't finish.
-        // In the input set this turns 15! into a lot smaller space.
-        if steps >= minutes {
-            continue;
-        }
+    /// Minutes spent teaching the elephants in part 2 before setting off.
+    #[arg(long, default_value_t = Team::WITH_ELEPHANT.training)]
+    training: usize,
 
-        let new_flow_rate = nodes[f].flow * (minutes - steps);
+    /// How many of you and the elephants open valves in part 2.
+    #[arg(long, default_value_t = Team::WITH_ELEPHANT.agents)]
+    agents: usize,
 
-        let rest = flows
-            .iter()
-            .filter(|v| **v != f)
-            .copied()
-            .collect::<Vec<_>>();
-        let mut new = find_best(f, &rest, nodes, paths, minutes - steps);
-        let mut p = Vec::from([f.clone()]);
-        p.append(&mut new.1);
-        choices.push((new_flow_rate + new.0, p));
-    }
-    choices
-        .iter()
-        .max()
-        .unwrap_or(&(0, Vec::from([String::from(cur)])))
-        .clone()
+    /// Tell the story of each part minute by minute, as the puzzle does.
+    #[arg(long, default_value_t = false)]
+    explain: bool,
 }
 
-#[allow(clippy::too_many_arguments)]
-fn find_best2(
-    cur: &str,
-    flows: &[&String],
-    nodes: &HashMap<&String, &Location>,
-    paths: &HashMap<String, Vec<&String>>,
-    minutes: usize,
-    cur_flow: usize,
-    cur_path: &mut Vec<String>,
-    depth: usize,
-    max_depth: usize,
-) -> (usize, Vec<String>) {
-    let mut choices = Vec::new();
-    cur_path.push(String::from(cur));
-    for f in flows.iter().copied() {
-        if depth < max_depth {
-            let key = format!("{cur}->{f}");
-            let steps = paths[&key].len();
-
-            // Prune off branches that can't finish.
-            // In the input set this turns 15! into a lot smaller space.
-            if steps >= minutes {
-                continue;
-            }
+fn main() -> Result<()> {
+    color_eyre::install()?;
+    let args: Args = Args::parse();
+    let report = args.common.report(16);
 
-            let new_flow_rate = nodes[f].flow * (minutes - steps);
-            let rest = flows
-                .iter()
-                .filter(|v| **v != f)
-                .copied()
-                .collect::<Vec<_>>();
-            choices.push(find_best2(
-                f,
-                &rest,
-                nodes,
-                paths,
-                minutes - steps,
-                cur_flow + new_flow_rate,
-                cur_path,
-                depth + 1,
-                max_depth,
-            ));
-        } else {
-            // Here we've completed a valid P(X,R) based on depth. Now given the rest of
-            // the nodes send that off to find_best to get 2nd path. We're looking for the max
-            // of path1+path2 which isn't necessarily the max of "find the largest P(X,R) and then find
-            // the value of the remainder" as ordering/weight can cause the first one to be slightly
-            // under the max and the 2nd path picks up the slack.
-            let mut p = cur_path.clone();
-            p.push(f.clone());
-            // Might get here with minutes remaining since the previous one above this didn't know
-            // our depth was expiring. So we need to add on the remainder from its last flow rate.
-            // Thankfully we know that since the last node in cur_path is the one to lookup and add.
-            let mut cur_flow = cur_flow;
-            if minutes > 1 {
-                cur_flow += (minutes - 1) * nodes[&cur_path[cur_path.len() - 1]].flow;
-            }
-            let mut new = find_best(&cur_path[0], flows, nodes, paths, 26);
-            p.append(&mut new.1);
-            choices.push((new.0 + cur_flow, p));
-            break;
+    let file = args.common.input(env!("CARGO_MANIFEST_DIR"))?;
+    let valves = file.parse(parse)?;
+    if args.common.debug {
+        for (k, v) in &valves {
+            report.show(format_args!("{k} - {v:?}"));
         }
     }
-    cur_path.pop();
-    choices
-        .iter()
-        .max()
-        .unwrap_or(&(0, Vec::from([String::from(cur)])))
-        .clone()
-}
-
-fn find_path<'a>(
-    hm: &'a HashMap<String, Location>,
-    start: &'a String,
-    end: &'a String,
-) -> Vec<&'a String> {
-    let mut deq = VecDeque::new();
-    deq.push_back(Vec::from([start]));
-    let mut path;
-    while !deq.is_empty() {
-        path = deq.pop_front().unwrap();
-        let check = path[path.len() - 1];
-        if check == end {
-            return path;
+    let solo = Team {
+        start: &args.start,
+        minutes: args.minutes,
+        ..Team::SOLO
+    };
+    let elephants = Team {
+        agents: args.agents,
+        training: args.training,
+        ..solo
+    };
+    for (part, team) in [(1, solo), (2, elephants)] {
+        if !report.wants(part) {
+            continue;
         }
-
-        'outer: for n in &hm[check].neighbors {
-            // Check for loops. TODO()- use a hash
-            for check in &path {
-                if **check == *n {
-                    continue 'outer;
-                }
-            }
-            let mut newpath = path.clone();
-            newpath.push(n);
-            deq.push_back(newpath);
+        let mut routes = Vec::new();
+        let a = aoc_common::time(16, part, || {
+            let best;
+            (best, routes) = plan(&valves, team)?;
+            Ok(best)
+        })?;
+        let joined = routes.iter().map(|r| r.join(" -> ")).collect::<Vec<_>>();
+        report.print(
+            &format!("part{part} - "),
+            &a.with_detail(joined.join(" | ")),
+        )?;
+        if args.explain {
+            report.show(format_args!("\n{}", explain(&valves, team, &routes)?));
         }
     }
-    Vec::new()
+    Ok(())
 }
@@ -0,0 +1,4 @@
+//! day17 benchmarks.
+// Same simulation for both parts, 2022 rocks against 10^12 where the cycle
+// detection has to kick in.
+aoc_common::benches!(day17, [part1, part2]);
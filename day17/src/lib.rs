@@ -0,0 +1,380 @@
+//! day17 advent 2022
+use aoc_common::{lines, ParseError};
+use color_eyre::eyre::Result;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use strum_macros::{Display, EnumCount as EnumCountMacro};
+
+/// A direction a rock gets pushed.
+#[derive(Clone, Debug, Display, Eq, Hash, PartialEq)]
+pub enum Dir {
+    /// Jet of gas pushing left.
+    Left,
+    /// Jet of gas pushing right.
+    Right,
+    /// Falling one unit.
+    Down,
+}
+
+#[derive(Clone, Debug, Display, EnumCountMacro, Eq, Hash, PartialEq)]
+enum Rock {
+    HLine,
+    Plus,
+    Corner,
+    VLine,
+    Square,
+}
+
+impl Rock {
+    fn next(self) -> Self {
+        match self {
+            Rock::HLine => Rock::Plus,
+            Rock::Plus => Rock::Corner,
+            Rock::Corner => Rock::VLine,
+            Rock::VLine => Rock::Square,
+            Rock::Square => Rock::HLine,
+        }
+    }
+
+    fn height(&self) -> usize {
+        match self {
+            Rock::HLine => 1,
+            Rock::Plus | Rock::Corner => 3,
+            Rock::VLine => 4,
+            Rock::Square => 2,
+        }
+    }
+
+    fn covers(&self, bottom_left: &Location) -> HashSet<Location> {
+        let mut v = HashSet::new();
+        match self {
+            Rock::HLine => {
+                for x in 0..4 {
+                    v.insert(Location(bottom_left.0 + x, bottom_left.1));
+                }
+            }
+            Rock::Plus => {
+                for x in 0..3 {
+                    v.insert(Location(bottom_left.0 + x, bottom_left.1 + 1));
+                }
+                v.insert(Location(bottom_left.0 + 1, bottom_left.1));
+                v.insert(Location(bottom_left.0 + 1, bottom_left.1 + 2));
+            }
+            Rock::Corner => {
+                for x in 0..3 {
+                    v.insert(Location(bottom_left.0 + x, bottom_left.1));
+                }
+                for y in 1..3 {
+                    v.insert(Location(bottom_left.0 + 2, bottom_left.1 + y));
+                }
+            }
+            Rock::VLine => {
+                for y in 0..4 {
+                    v.insert(Location(bottom_left.0, bottom_left.1 + y));
+                }
+            }
+            Rock::Square => {
+                for x in 0..2 {
+                    v.insert(Location(bottom_left.0 + x, bottom_left.1));
+                }
+                for x in 0..2 {
+                    v.insert(Location(bottom_left.0 + x, bottom_left.1 + 1));
+                }
+            }
+        }
+        v
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Location(usize, usize);
+
+impl PartialOrd for Location {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Location {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.1 == other.1 {
+            match self.0.cmp(&other.0) {
+                Ordering::Less => Ordering::Greater,
+                Ordering::Equal => Ordering::Equal,
+                Ordering::Greater => Ordering::Less,
+            }
+        } else {
+            self.1.cmp(&other.1)
+        }
+    }
+}
+
+/// The jet pattern.
+pub type Input = Vec<Dir>;
+
+/// Parse the jet pattern.
+///
+/// # Errors
+/// On anything other than `<` or `>` or if there's no pattern at all.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut air = Vec::new();
+    for (line_num, line) in lines(input) {
+        for (pos, i) in line.as_bytes().iter().enumerate() {
+            match i {
+                b'>' => {
+                    air.push(Dir::Right);
+                }
+                b'<' => {
+                    air.push(Dir::Left);
+                }
+                _ => {
+                    return Err(ParseError::new(
+                        line_num,
+                        line,
+                        line.get(pos..=pos).unwrap_or(&line[pos..]),
+                        "`<` or `>`",
+                    )
+                    .into());
+                }
+            }
+        }
+    }
+    if air.is_empty() {
+        let last = lines(input).last().unwrap_or((0, ""));
+        return Err(ParseError::end_of_input(last.0, last.1, "`<` or `>`").into());
+    }
+    Ok(air)
+}
+
+/// Height of the tower after 2022 rocks.
+///
+/// # Errors
+/// None, parsing already made sure there's a jet pattern.
+pub fn part1(air: &Input) -> Result<usize> {
+    Ok(compute(2022, false, air))
+}
+
+/// Height of the tower after 1000000000000 rocks.
+///
+/// # Errors
+/// None, as for [`part1`].
+pub fn part2(air: &Input) -> Result<usize> {
+    Ok(compute(1_000_000_000_000, false, air))
+}
+
+/// Height of the tower after `iterations` rocks have fallen. `print_each`
+/// draws the chamber after every move.
+///
+/// # Panics
+/// If `air` is empty.
+#[must_use]
+pub fn compute(iterations: usize, print_each: bool, air: &[Dir]) -> usize {
+    simulate(iterations, print_each, air).0
+}
+
+/// The rocks either side of the period [`compute`] skips ahead with for
+/// `iterations` rocks, or `None` if it drops every one.
+///
+/// # Panics
+/// If `air` is empty.
+#[must_use]
+pub fn period(iterations: usize, air: &[Dir]) -> Option<(usize, usize)> {
+    simulate(iterations, false, air).1
+}
+
+// The height of the tower and the period used to get there if any.
+fn simulate(iterations: usize, print_each: bool, air: &[Dir]) -> (usize, Option<(usize, usize)>) {
+    let mut filled = HashSet::new();
+    for x in &[0, 8] {
+        for y in 0..=4 {
+            filled.insert(Location(*x, y));
+        }
+    }
+    for x in 0..=8 {
+        filled.insert(Location(x, 0));
+    }
+    print_board(print_each, &filled, &HashSet::new());
+
+    let mut highest = 0;
+    let mut start_y;
+    let start_x = 3;
+    let mut cur = Rock::Square;
+    let mut air_pos = 0;
+    let mut tracked: HashMap<(Rock, usize), (usize, usize)> = HashMap::new();
+    for i in 0..iterations {
+        cur = cur.next();
+        start_y = highest + 4;
+        for y in start_y..start_y + cur.height() {
+            filled.insert(Location(0, y));
+            filled.insert(Location(8, y));
+        }
+
+        // We have 5 rocks and some airflow pattern that will eventually repeat a pattern.
+        // Technically there are many cycles contained in various places. We want a specific one.
+        // Even if the air flow vector is huge there's a limit since it only has 2 cases in it.
+        // So height grows less than the number of air moves we make.
+        //
+        // Assume if we've dropped 1000 rocks we're past the point where the original bottom
+        // (which is 100% covered) is not part of our period.
+        //
+        // At this point starting tracking Rock and the current position in the air vec.
+        // This will eventually repeat itself. Check if this is our cycle by seeing if
+        // the current iteration mod period equals total iterations mod period. If that's
+        // the case we have an integer number of periods to go which is easy to compute.
+        // By sliding around looking for this we eventually find the magic period which
+        // means the height at period start + (remaining periods * height diff) == total.
+        // Only look once per rock, as a short jet pattern can come back to the
+        // same position while a single rock is still falling.
+        if i > 1000 {
+            let key = (cur.clone(), air_pos);
+            if let std::collections::hash_map::Entry::Vacant(e) = tracked.entry(key.clone()) {
+                e.insert((i, highest));
+            } else {
+                let v = tracked[&key];
+                let period = i - v.0;
+                if i % period == iterations % period {
+                    let h = highest - v.1;
+                    let remaining = iterations - i;
+                    let c = (remaining / period) + 1;
+                    return (v.1 + (h * c), Some((v.0, i)));
+                }
+            }
+        }
+
+        let mut covered = cur.covers(&Location(start_x, start_y));
+        print_board(print_each, &filled, &covered);
+
+        loop {
+            let a = &air[air_pos];
+            air_pos += 1;
+            if air_pos >= air.len() {
+                air_pos = 0;
+            }
+
+            // We don't care for the air direction if we moved or not.
+            // Just make sure covered is accurate in case we did.
+            check_move(a, &mut covered, &filled);
+            print_board(print_each, &filled, &covered);
+
+            // For moving down if it can't we know to build up.
+            if !check_move(&Dir::Down, &mut covered, &filled) {
+                let mut max = 0;
+                for c in &covered {
+                    filled.insert(c.clone());
+                }
+                for n in filled.iter().filter(|l| l.0 > 0 && l.0 < 8 && l.1 != 0) {
+                    if n.1 > max {
+                        max = n.1;
+                    }
+                }
+                highest = max;
+                break;
+            }
+            print_board(print_each, &filled, &covered);
+        }
+    }
+    (highest, None)
+}
+
+fn check_move(a: &Dir, covered: &mut HashSet<Location>, filled: &HashSet<Location>) -> bool {
+    let mut to_move = true;
+    for c in covered.iter() {
+        let check = match a {
+            Dir::Left => Location(c.0 - 1, c.1),
+            Dir::Right => Location(c.0 + 1, c.1),
+            Dir::Down => Location(c.0, c.1 - 1),
+        };
+        if filled.contains(&check) {
+            to_move = false;
+            break;
+        }
+    }
+    if to_move {
+        let mut new = HashSet::new();
+        for c in covered.iter() {
+            let check = match a {
+                Dir::Left => Location(c.0 - 1, c.1),
+                Dir::Right => Location(c.0 + 1, c.1),
+                Dir::Down => Location(c.0, c.1 - 1),
+            };
+            new.insert(check);
+        }
+        *covered = new;
+    }
+    to_move
+}
+
+fn print_board(print: bool, filled: &HashSet<Location>, covered: &HashSet<Location>) {
+    if print {
+        let mut combined = filled.clone();
+        combined = combined.union(covered).cloned().collect();
+        let mut points = combined.iter().collect::<Vec<_>>();
+        points.sort();
+        let mut cur_y = usize::MAX;
+        let mut cur_x = usize::MIN;
+        for p in points.iter().copied().rev() {
+            if p.1 < cur_y {
+                println!();
+                cur_x = 0;
+                cur_y = p.1;
+            }
+            if p.0 - cur_x > 1 {
+                for _ in cur_x..p.0 - 1 {
+                    print!(" ");
+                }
+            }
+            print!("#");
+            cur_x = p.0;
+        }
+        println!();
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 3068);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 1_514_285_714_288);
+        Ok(())
+    }
+
+    #[test]
+    fn example_period() -> Result<()> {
+        let air = parse(EXAMPLE)?;
+        assert_eq!(period(1000, &air), None);
+        let (start, end) = period(1_000_000_000_000, &air).unwrap();
+        assert!(start > 1000 && (end - start) % 35 == 0);
+        Ok(())
+    }
+
+    #[test]
+    fn no_jets() {
+        assert!(parse("\n").unwrap_err().is::<ParseError>());
+    }
+
+    #[test]
+    fn short_jets() -> Result<()> {
+        // With a jet pattern this short the tower repeats every 5 rocks.
+        for (jets, want1, want2) in [
+            ("<", 4448, 2_200_000_000_000),
+            ("<>", 4448, 2_200_000_000_000),
+            (">>", 5256, 2_600_000_000_000),
+        ] {
+            let air = parse(jets)?;
+            assert_eq!(part1(&air)?, want1, "{jets}");
+            assert_eq!(part2(&air)?, want2, "{jets}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+//! day18 benchmarks.
+aoc_common::benches!(day18, [part1, part2]);
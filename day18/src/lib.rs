@@ -0,0 +1,174 @@
+//! day18 advent 2022
+use aoc_common::{lines, ParseError};
+use color_eyre::eyre::Result;
+use std::collections::HashSet;
+
+/// An x,y,z position of a lava cube.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Location(pub i64, pub i64, pub i64);
+
+/// The scanned lava cubes.
+pub type Input = Vec<Location>;
+
+/// Parse the cube positions.
+///
+/// # Errors
+/// If a line isn't 3 comma separated coordinates.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut squares = Vec::new();
+    for (line_num, line) in lines(input) {
+        let parts = line.split(',').collect::<Vec<_>>();
+        if parts.len() < 3 {
+            return Err(ParseError::end_of_line(line_num, line, "`,`").into());
+        }
+        if let Some(extra) = parts.get(3) {
+            return Err(ParseError::new(line_num, line, extra, "end of line").into());
+        }
+        let x = ParseError::parse(line_num, line, parts[0], "a coordinate")?;
+        let y = ParseError::parse(line_num, line, parts[1], "a coordinate")?;
+        let z = ParseError::parse(line_num, line, parts[2], "a coordinate")?;
+        squares.push(Location(x, y, z));
+    }
+    Ok(squares)
+}
+
+/// Surface area of the droplet including trapped air pockets.
+///
+/// # Errors
+/// None, an empty droplet just has no surface.
+pub fn part1(squares: &Input) -> Result<usize> {
+    // If nothing touches we get 6 faces per square.
+    let mut faces = squares.len() * 6;
+    for i in 0..squares.len() {
+        for s in squares.iter().skip(i + 1) {
+            faces -= touching(&squares[i], s);
+        }
+    }
+    Ok(faces)
+}
+
+/// Exterior surface area of the droplet.
+///
+/// # Errors
+/// None, as for [`part1`].
+pub fn part2(squares: &Input) -> Result<usize> {
+    let mut lava = HashSet::new();
+    for v in squares {
+        lava.insert(v.clone());
+    }
+    let (mut max_x, mut max_y, mut max_z) = (i64::MIN, i64::MIN, i64::MIN);
+    let (mut min_x, mut min_y, mut min_z) = (i64::MAX, i64::MAX, i64::MAX);
+    for compare in squares {
+        if compare.0 > max_x {
+            max_x = compare.0;
+        }
+        if compare.1 > max_y {
+            max_y = compare.1;
+        }
+        if compare.2 > max_z {
+            max_z = compare.2;
+        }
+        if compare.0 < min_x {
+            min_x = compare.0;
+        }
+        if compare.1 < min_y {
+            min_y = compare.1;
+        }
+        if compare.2 < min_z {
+            min_z = compare.2;
+        }
+    }
+    // Make a bounding box one larger to surround this.
+    min_x -= 1;
+    min_y -= 1;
+    min_z -= 1;
+    max_x += 1;
+    max_y += 1;
+    max_z += 1;
+
+    let mut choices = Vec::from([Location(min_x, min_y, min_z)]);
+    let mut water = HashSet::new();
+    while let Some(cur) = choices.pop() {
+        for dir in &[
+            Location(cur.0 + 1, cur.1, cur.2),
+            Location(cur.0 - 1, cur.1, cur.2),
+            Location(cur.0, cur.1 + 1, cur.2),
+            Location(cur.0, cur.1 - 1, cur.2),
+            Location(cur.0, cur.1, cur.2 + 1),
+            Location(cur.0, cur.1, cur.2 - 1),
+        ] {
+            // If this has lava we don't fill through it.
+            if lava.contains(dir) {
+                continue;
+            }
+            // Outside box. Just skip.
+            if dir.0 < min_x
+                || dir.0 > max_x
+                || dir.1 < min_y
+                || dir.1 > max_y
+                || dir.2 < min_z
+                || dir.2 > max_z
+            {
+                continue;
+            }
+
+            if !water.contains(dir) {
+                water.insert(dir.clone());
+                choices.push(dir.clone());
+            }
+        }
+    }
+
+    let mut faces2 = 0;
+    for cur in &lava {
+        for dir in &[
+            Location(cur.0 + 1, cur.1, cur.2),
+            Location(cur.0 - 1, cur.1, cur.2),
+            Location(cur.0, cur.1 + 1, cur.2),
+            Location(cur.0, cur.1 - 1, cur.2),
+            Location(cur.0, cur.1, cur.2 + 1),
+            Location(cur.0, cur.1, cur.2 - 1),
+        ] {
+            if water.contains(dir) {
+                faces2 += 1;
+            }
+        }
+    }
+    Ok(faces2)
+}
+
+fn touching(entry: &Location, compare: &Location) -> usize {
+    let mut tot = 0;
+    // Check x side
+    if (entry.0 - compare.0).abs() == 1 && entry.1 == compare.1 && entry.2 == compare.2 {
+        tot += 2;
+    }
+    // Check y side
+    if (entry.1 - compare.1).abs() == 1 && entry.0 == compare.0 && entry.2 == compare.2 {
+        tot += 2;
+    }
+    // Check z side
+    if (entry.2 - compare.2).abs() == 1 && entry.0 == compare.0 && entry.1 == compare.1 {
+        tot += 2;
+    }
+    tot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 64);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 58);
+        Ok(())
+    }
+}
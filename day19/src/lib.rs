@@ -0,0 +1,272 @@
+//! day19 advent 2022
+use aoc_common::{lines, ParseError};
+use color_eyre::eyre::Result;
+use strum_macros::{Display, EnumCount as EnumCountMacro};
+
+/// A robot and what it costs to build.
+#[derive(Clone, Debug, Display, EnumCountMacro, Eq, Hash, PartialEq)]
+pub enum Robot {
+    /// Costs ore.
+    Ore(usize),
+    /// Costs ore.
+    Clay(usize),
+    /// Costs ore and clay.
+    Obsidion(usize, usize),
+    /// Costs ore and obsidian.
+    Geode(usize, usize),
+}
+
+/// Each blueprint's robots in the order ore, clay, obsidian, geode.
+pub type Input = Vec<Vec<Robot>>;
+
+/// Parse the blueprints.
+///
+/// # Errors
+/// On a malformed line or if there are no blueprints.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut blueprints = Vec::new();
+    for (line_num, line) in lines(input) {
+        let parts = line.split_whitespace().collect::<Vec<_>>();
+        if parts.len() < 32 {
+            return Err(
+                ParseError::end_of_line(line_num, line, "the rest of the blueprint").into(),
+            );
+        }
+        if let Some(extra) = parts.get(32) {
+            return Err(ParseError::new(line_num, line, extra, "end of line").into());
+        }
+        let cost = |i: usize| ParseError::parse::<usize>(line_num, line, parts[i], "a cost");
+
+        let mut entry = Vec::new();
+
+        let ore = Robot::Ore(cost(6)?);
+        entry.push(ore);
+        let clay = Robot::Clay(cost(12)?);
+        entry.push(clay);
+        let obsidion = Robot::Obsidion(cost(18)?, cost(21)?);
+        entry.push(obsidion);
+        let geode = Robot::Geode(cost(27)?, cost(30)?);
+        entry.push(geode);
+        blueprints.push(entry);
+    }
+    if blueprints.is_empty() {
+        return Err(ParseError::end_of_input(0, "", "a blueprint").into());
+    }
+    Ok(blueprints)
+}
+
+/// Sum of the quality levels of every blueprint over 24 minutes.
+///
+/// # Errors
+/// None, every blueprint opens some number of geodes, even if it's 0.
+pub fn part1(blueprints: &Input) -> Result<usize> {
+    Ok(quality_sum(blueprints, 24))
+}
+
+/// Product of the geodes the first 3 blueprints open in 32 minutes.
+///
+/// # Errors
+/// None, as for [`part1`].
+pub fn part2(blueprints: &Input) -> Result<usize> {
+    Ok(geode_product(blueprints, 32))
+}
+
+/// Most geodes `blueprint` can open in `turns` minutes. A robot missing
+/// from it costs nothing and a repeated one costs what it did last.
+#[must_use]
+pub fn geodes(blueprint: &[Robot], turns: usize) -> usize {
+    let mut costs = Costs::default();
+    for r in blueprint {
+        match *r {
+            Robot::Ore(o) => costs.ore_ore = o,
+            Robot::Clay(o) => costs.clay_ore = o,
+            Robot::Obsidion(o, c) => (costs.obs_ore, costs.obs_clay) = (o, c),
+            Robot::Geode(o, ob) => (costs.geode_ore, costs.geode_obs) = (o, ob),
+        }
+    }
+    let max_ore = costs
+        .ore_ore
+        .max(costs.clay_ore)
+        .max(costs.obs_ore)
+        .max(costs.geode_ore);
+    build(turns, max_ore, &costs, [0, 0, 0, 0], [1, 0, 0, 0])
+}
+
+// What each robot costs, pulled out of the blueprint once.
+#[derive(Default)]
+struct Costs {
+    ore_ore: usize,
+    clay_ore: usize,
+    obs_ore: usize,
+    obs_clay: usize,
+    geode_ore: usize,
+    geode_obs: usize,
+}
+
+/// Sum of each blueprint's id times the geodes it opens in `turns` minutes.
+#[must_use]
+pub fn quality_sum(blueprints: &[Vec<Robot>], turns: usize) -> usize {
+    let mut sum = 0;
+    for (i, b) in blueprints.iter().enumerate() {
+        sum += (i + 1) * geodes(b, turns);
+    }
+    sum
+}
+
+/// Product of the geodes the first 3 blueprints open in `turns` minutes.
+#[must_use]
+pub fn geode_product(blueprints: &[Vec<Robot>], turns: usize) -> usize {
+    let mut sum = 1;
+    for b in blueprints.iter().take(3) {
+        sum *= geodes(b, turns);
+    }
+    sum
+}
+
+#[allow(clippy::too_many_lines)]
+fn build(
+    turns: usize,
+    max_ore: usize,
+    blueprint: &Costs,
+    rocks: [usize; 4],
+    robots: [usize; 4],
+) -> usize {
+    // No turns left return the geodes we've mined.
+    if turns == 0 {
+        return rocks[3];
+    }
+    let mut choices = Vec::new();
+    let &Costs {
+        ore_ore,
+        clay_ore,
+        obs_ore,
+        obs_clay,
+        geode_ore,
+        geode_obs,
+    } = blueprint;
+    let (new_ore, new_clay, new_obsidion, new_geode) = (robots[0], robots[1], robots[2], robots[3]);
+    if turns > 1 && rocks[0] >= geode_ore && rocks[2] >= geode_obs {
+        // Check geode robot first.
+        choices.push(build(
+            turns - 1,
+            max_ore,
+            blueprint,
+            [
+                rocks[0] - geode_ore + new_ore,
+                rocks[1] + new_clay,
+                rocks[2] - geode_obs + new_obsidion,
+                rocks[3] + new_geode,
+            ],
+            [robots[0], robots[1], robots[2], robots[3] + 1],
+        ));
+    } else {
+        if turns > 2
+            && rocks[0] >= obs_ore
+            && rocks[1] >= obs_clay
+            && robots[2] < geode_obs
+            && (robots[2] * turns + rocks[2] < turns * geode_obs)
+        {
+            // Check obsidion robot.
+            choices.push(build(
+                turns - 1,
+                max_ore,
+                blueprint,
+                [
+                    rocks[0] - obs_ore + new_ore,
+                    rocks[1] - obs_clay + new_clay,
+                    rocks[2] + new_obsidion,
+                    rocks[3] + new_geode,
+                ],
+                [robots[0], robots[1], robots[2] + 1, robots[3]],
+            ));
+        }
+        if turns > 6
+            && rocks[0] >= clay_ore
+            && robots[1] < obs_clay
+            && (robots[1] * turns + rocks[1] < turns * obs_clay)
+        {
+            // Check clay robot.
+            choices.push(build(
+                turns - 1,
+                max_ore,
+                blueprint,
+                [
+                    rocks[0] - clay_ore + new_ore,
+                    rocks[1] + new_clay,
+                    rocks[2] + new_obsidion,
+                    rocks[3] + new_geode,
+                ],
+                [robots[0], robots[1] + 1, robots[2], robots[3]],
+            ));
+        }
+        if turns > 2
+            && rocks[0] >= ore_ore
+            && robots[0] < max_ore
+            && (robots[0] * turns + rocks[0] < turns * max_ore)
+        {
+            // Finally check ore robot.
+            choices.push(build(
+                turns - 1,
+                max_ore,
+                blueprint,
+                [
+                    rocks[0] - ore_ore + new_ore,
+                    rocks[1] + new_clay,
+                    rocks[2] + new_obsidion,
+                    rocks[3] + new_geode,
+                ],
+                [robots[0] + 1, robots[1], robots[2], robots[3]],
+            ));
+        } else {
+            // Always do a run where just wait
+            choices.push(build(
+                turns - 1,
+                max_ore,
+                blueprint,
+                [
+                    rocks[0] + new_ore,
+                    rocks[1] + new_clay,
+                    rocks[2] + new_obsidion,
+                    rocks[3] + new_geode,
+                ],
+                [robots[0], robots[1], robots[2], robots[3]],
+            ));
+        }
+    }
+    *choices.iter().max().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn each_blueprint() -> Result<()> {
+        let blueprints = parse(EXAMPLE)?;
+        assert_eq!(geodes(&blueprints[0], 24), 9);
+        assert_eq!(quality_sum(&blueprints[..1], 24), 9);
+        assert_eq!(geodes(&blueprints[1], 24), 12);
+        Ok(())
+    }
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 33);
+        Ok(())
+    }
+
+    // Run with `cargo test -- --ignored`.
+    #[test]
+    #[ignore = "32 minutes for the example's 2 blueprints takes about a minute"]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 3472);
+        Ok(())
+    }
+
+    #[test]
+    fn no_blueprints() {
+        assert!(parse("").unwrap_err().is::<ParseError>());
+    }
+}
@@ -0,0 +1,2 @@
+//! day2 benchmarks.
+aoc_common::benches!(day2, [part1, part2]);
@@ -0,0 +1,141 @@
+//! day2 advent 2022
+use aoc_common::{lines, ParseError};
+use color_eyre::eyre::Result;
+
+/// A rock/paper/scissors throw.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Rps {
+    /// Rock (A or X)
+    Rock,
+    /// Paper (B or Y)
+    Paper,
+    /// Scissors (C or Z)
+    Scissors,
+}
+
+/// How a round should end.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Res {
+    /// Lose (X)
+    Lose,
+    /// Draw (Y)
+    Draw,
+    /// Win (Z)
+    Win,
+}
+
+/// One line of the strategy guide. The second column is read both ways
+/// since part 1 and part 2 disagree on what it means.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Round {
+    /// What the opponent throws.
+    pub play1: Rps,
+    /// Second column read as our throw.
+    pub play2: Rps,
+    /// Second column read as the result we want.
+    pub exp: Res,
+}
+
+/// The parsed strategy guide.
+pub type Input = Vec<Round>;
+
+/// Parse the strategy guide.
+///
+/// # Errors
+/// If a line isn't a valid throw followed by a valid response.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut rounds = Vec::new();
+    for (line_num, line) in lines(input) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() > 2 {
+            return Err(ParseError::new(line_num, line, fields[2], "end of line").into());
+        }
+
+        let play1 = match fields.first().copied().unwrap_or_default() {
+            "A" => Rps::Rock,
+            "B" => Rps::Paper,
+            "C" => Rps::Scissors,
+            f => return Err(ParseError::new(line_num, line, f, "one of `A`, `B` or `C`").into()),
+        };
+        let (play2, exp) = match fields.get(1).copied().unwrap_or_default() {
+            "X" => (Rps::Rock, Res::Lose),
+            "Y" => (Rps::Paper, Res::Draw),
+            "Z" => (Rps::Scissors, Res::Win),
+            f => return Err(ParseError::new(line_num, line, f, "one of `X`, `Y` or `Z`").into()),
+        };
+        rounds.push(Round { play1, play2, exp });
+    }
+    Ok(rounds)
+}
+
+/// Total score treating the second column as our throw.
+///
+/// # Errors
+/// None, every round scores.
+pub fn part1(rounds: &Input) -> Result<u32> {
+    let mut score = 0;
+    for r in rounds {
+        score += match (&r.play1, &r.play2) {
+            (Rps::Rock, Rps::Rock) => 1 + 3,
+            (Rps::Rock, Rps::Paper) => 2 + 6,
+            (Rps::Rock, Rps::Scissors) => 3, //  + 0,
+            (Rps::Paper, Rps::Rock) => 1,    //  + 0,
+            (Rps::Paper, Rps::Paper) => 2 + 3,
+            (Rps::Paper, Rps::Scissors) => 3 + 6,
+            (Rps::Scissors, Rps::Rock) => 1 + 6,
+            (Rps::Scissors, Rps::Paper) => 2, //  + 0,
+            (Rps::Scissors, Rps::Scissors) => 3 + 3,
+        };
+    }
+    Ok(score)
+}
+
+/// Total score treating the second column as the result we need.
+///
+/// # Errors
+/// None, every result can be reached.
+pub fn part2(rounds: &Input) -> Result<u32> {
+    let mut score = 0;
+    for r in rounds {
+        score += match (&r.play1, &r.exp) {
+            (Rps::Rock, Res::Lose) => 3, // + 0,
+            (Rps::Rock, Res::Draw) => 1 + 3,
+            (Rps::Rock, Res::Win) => 2 + 6,
+            (Rps::Paper, Res::Lose) => 1, //  + 0,
+            (Rps::Paper, Res::Draw) => 2 + 3,
+            (Rps::Paper, Res::Win) => 3 + 6,
+            (Rps::Scissors, Res::Lose) => 2, //  + 0,
+            (Rps::Scissors, Res::Draw) => 3 + 3,
+            (Rps::Scissors, Res::Win) => 1 + 6,
+        }
+    }
+    Ok(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 15);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 12);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_throw() -> Result<()> {
+        let err = parse("A Y\nB Q\n").unwrap_err().downcast::<ParseError>()?;
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "one of `X`, `Y` or `Z`");
+        assert_eq!(err.found, "`Q`");
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+//! day20 benchmarks.
+// One of the slow days, keep the run time reasonable.
+aoc_common::benches!(day20, [part1, part2], sample_size = 10);
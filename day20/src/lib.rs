@@ -0,0 +1,125 @@
+//! day20 advent 2022
+use aoc_common::{lines, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+
+/// The encrypted file.
+pub type Input = Vec<i64>;
+
+/// Parse the numbers, one per line.
+///
+/// # Errors
+/// If a number doesn't parse.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut nums = Vec::new();
+    for (line_num, line) in lines(input) {
+        nums.push(ParseError::parse(line_num, line, line, "a number")?);
+    }
+    Ok(nums)
+}
+
+/// Sum of the grove coordinates after mixing once.
+///
+/// # Errors
+/// See [`decrypt`].
+pub fn part1(nums: &Input) -> Result<i64> {
+    decrypt(nums, 1, 1)
+}
+
+/// Sum of the grove coordinates after applying the key and mixing 10 times.
+///
+/// # Errors
+/// See [`decrypt`].
+pub fn part2(nums: &Input) -> Result<i64> {
+    decrypt(nums, 811_589_153, 10)
+}
+
+/// Multiply every number by `scale`, mix `rounds` times and return the sum
+/// of the numbers 1000, 2000 and 3000 after the 0.
+///
+/// # Errors
+/// If there's no 0 to count from (which includes an empty file) or an
+/// index doesn't fit.
+pub fn decrypt(nums: &[i64], scale: i64, rounds: usize) -> Result<i64> {
+    let Some(pos) = nums.iter().position(|n| *n == 0) else {
+        return Err(NoAnswer::new("no 0 to count the grove coordinates from").into());
+    };
+    let mut input = Vec::new();
+    for val in nums {
+        input.push(val * scale);
+    }
+    let mut indexes = Vec::new();
+    for i in 0..input.len() {
+        indexes.push(i);
+    }
+    // We're modding indexes which are 0 based, not 1 so subtract 1.
+    let len = i64::try_from(indexes.len())? - 1;
+    for _ in 0..rounds {
+        for (pos, i) in input.iter().enumerate() {
+            let cur = indexes[pos];
+
+            if *i == 0 {
+                continue;
+            }
+
+            let mut new = i64::try_from(cur)? + i;
+            // This mess deals with going negative.
+            // Move into the right range then add and mod off to get positive if needed.
+            new = ((new % len) + len) % len;
+
+            // Now back to something we can assign.
+            let a: usize = new.try_into()?;
+            indexes[pos] = a;
+
+            for (i, v) in indexes.iter_mut().enumerate() {
+                // If it moved right
+                if i != pos && *v >= cur && *v <= new.try_into()? {
+                    *v -= 1;
+                    continue;
+                }
+                if i != pos && *v >= new.try_into()? && *v < cur {
+                    *v += 1;
+                }
+            }
+        }
+    }
+    let f = make_indexes(&input, &indexes);
+    let zero = indexes[pos];
+    let thousand = f[(zero + 1000) % indexes.len()];
+    let two_thousand = f[(zero + 2000) % indexes.len()];
+    let three_thousand = f[(zero + 3000) % indexes.len()];
+    Ok(thousand + two_thousand + three_thousand)
+}
+
+fn make_indexes(input: &[i64], indexes: &[usize]) -> Vec<i64> {
+    let mut fixed = vec![0; indexes.len()];
+    for (pos, i) in indexes.iter().enumerate() {
+        fixed[*i] = input[pos];
+    }
+    fixed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 1_623_178_306);
+        Ok(())
+    }
+
+    #[test]
+    fn no_zero() -> Result<()> {
+        assert!(part1(&parse("1\n2\n")?).unwrap_err().is::<NoAnswer>());
+        assert!(part1(&Vec::new()).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+}
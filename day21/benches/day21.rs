@@ -0,0 +1,2 @@
+//! day21 benchmarks.
+aoc_common::benches!(day21, [part1, part2]);
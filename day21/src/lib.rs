@@ -0,0 +1,410 @@
+//! day21 advent 2022
+use aoc_common::{lines, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use slab_tree::tree::Tree;
+use std::collections::HashMap;
+use strum_macros::Display;
+
+#[derive(Clone, Debug, Display, Eq, Hash, PartialEq)]
+enum Operation {
+    Plus,
+    Minus,
+    Multiply,
+    Divide,
+    Unknown,
+}
+
+#[derive(Clone, Debug, Display, Eq, Hash, PartialEq)]
+enum Item {
+    Value(i64),
+    Operation(Operation),
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Entry<'a> {
+    name: &'a str,
+    value: Item,
+}
+
+/// One monkey's job: a number or an operation on two other monkeys.
+#[derive(Clone, Debug)]
+pub struct Definition<'a> {
+    children: Vec<&'a str>,
+    op: Entry<'a>,
+}
+
+/// The monkeys by name.
+pub type Input<'a> = HashMap<&'a str, Definition<'a>>;
+
+/// Parse the monkey jobs.
+///
+/// # Errors
+/// On a malformed line.
+pub fn parse(input: &str) -> Result<Input<'_>> {
+    let mut hm = HashMap::new();
+    for (line_num, line) in lines(input) {
+        let parts = line.split_whitespace().collect::<Vec<_>>();
+
+        let Some(key) = parts.first().and_then(|p| p.strip_suffix(':')) else {
+            return Err(ParseError::new(
+                line_num,
+                line,
+                parts.first().copied().unwrap_or_default(),
+                "a monkey name followed by `:`",
+            )
+            .into());
+        };
+        match parts.len() {
+            2 => {
+                let val = ParseError::parse(line_num, line, parts[1], "a number")?;
+                hm.insert(
+                    key,
+                    Definition {
+                        children: vec![],
+                        op: Entry {
+                            name: key,
+                            value: Item::Value(val),
+                        },
+                    },
+                );
+            }
+            4 => {
+                let op = match parts[2] {
+                    "+" => Operation::Plus,
+                    "-" => Operation::Minus,
+                    "*" => Operation::Multiply,
+                    "/" => Operation::Divide,
+                    p => {
+                        return Err(ParseError::new(
+                            line_num,
+                            line,
+                            p,
+                            "one of `+`, `-`, `*` or `/`",
+                        )
+                        .into());
+                    }
+                };
+                hm.insert(
+                    key,
+                    Definition {
+                        children: vec![parts[1], parts[3]],
+                        op: Entry {
+                            name: key,
+                            value: Item::Operation(op),
+                        },
+                    },
+                );
+            }
+            1 => {
+                return Err(
+                    ParseError::end_of_line(line_num, line, "a number or an operation").into(),
+                );
+            }
+            3 => return Err(ParseError::end_of_line(line_num, line, "a monkey name").into()),
+            _ => return Err(ParseError::new(line_num, line, parts[4], "end of line").into()),
+        }
+    }
+    Ok(hm)
+}
+
+/// Number the root monkey yells.
+///
+/// # Errors
+/// If there's no root, a monkey waits on one that doesn't exist or there's
+/// a division by zero.
+pub fn part1(hm: &Input) -> Result<i64> {
+    let mut tree = make_tree(hm)?;
+    transform_tree(&mut tree, false)?;
+    match tree.root().ok_or_else(no_root)?.data().value {
+        Item::Value(v) => Ok(v),
+        Item::Operation(_) => Err(NoAnswer::new("root doesn't work out to a number").into()),
+    }
+}
+
+/// Number humn has to yell so both sides of root match.
+///
+/// # Errors
+/// As for [`part1`] and also if humn isn't reachable from root through a
+/// single chain of operations.
+pub fn part2(hm: &Input) -> Result<i64> {
+    let mut tree = make_tree(hm)?;
+    transform_tree(&mut tree, true)?;
+    let unsolvable = || NoAnswer::new("humn isn't on exactly one side of root");
+
+    let mut cur = 0;
+    let mut node = tree.root().ok_or_else(no_root)?;
+    for c in tree.root().ok_or_else(no_root)?.children() {
+        if let Item::Value(v) = c.data().value {
+            // Normally not good but we know the input data fits
+            cur = v;
+            continue;
+        }
+        if let Item::Operation(_) = c.data().value {
+            node = c;
+        }
+    }
+    loop {
+        // Each step is
+        //
+        // cur = x op value
+        //
+        // or
+        //
+        // cur = value op x
+        //
+        // Invert that so cur becomes a new value based on inverse of op and value
+        // and set child to the node which is the op.
+        // Stop when we find the unknown node and report cur.
+        let op = match node.data().value.clone() {
+            // This is the humn node so we're done.
+            Item::Operation(Operation::Unknown) => break,
+            Item::Operation(op) => op,
+            Item::Value(_) => return Err(unsolvable().into()),
+        };
+        let mut children = node.children().collect::<Vec<_>>();
+        let v = if let Item::Value(v) = children[0].data().value {
+            v
+        } else if let Item::Value(v) = children[1].data().value {
+            v
+        } else {
+            return Err(unsolvable().into());
+        };
+        let idx: usize = if let Item::Operation(_) = children[0].data().value {
+            0
+        } else {
+            1
+        };
+        let child1_op = !matches!(children[0].data().value, Item::Operation(_));
+        match op {
+            Operation::Plus => {
+                // order here doesn't matter.
+                //
+                // cur = x + value
+                // x = cur - value
+                cur -= v;
+            }
+            Operation::Minus => {
+                // order here does matter.
+                //
+                // cur = x - value
+                // x = cur + value
+                //
+                // cur = value - x
+                // x = value - cur;
+                if child1_op {
+                    cur += v;
+                } else {
+                    cur = v - cur;
+                }
+            }
+            Operation::Multiply => {
+                // order here doesn't matter.
+                //
+                // cur = x * value
+                // x = cur / value
+                cur = cur.checked_div(v).ok_or_else(unsolvable)?;
+            }
+            Operation::Divide => {
+                // order here does matter.
+                //
+                // cur = x / value
+                // x = cur * value
+                //
+                // cur = value / x
+                // x = value / cur
+                if child1_op {
+                    cur *= v;
+                } else {
+                    cur = v.checked_div(cur).ok_or_else(unsolvable)?;
+                }
+            }
+            // Caught above before looking at the children.
+            Operation::Unknown => break,
+        }
+        node = children.swap_remove(idx);
+    }
+    Ok(cur)
+}
+
+/// Draw the monkeys as a tree from root with every number that doesn't
+/// depend on humn worked out, leaving the chain [`part2`] solves back up.
+///
+/// # Errors
+/// As for [`part1`].
+pub fn render(hm: &Input) -> Result<String> {
+    let mut tree = make_tree(hm)?;
+    transform_tree(&mut tree, true)?;
+    let mut s = String::new();
+    tree.write_formatted(&mut s)?;
+    Ok(s)
+}
+
+// make_tree always sets one, but an empty tree shouldn't panic if that changes.
+fn no_root() -> NoAnswer {
+    NoAnswer::new("the tree has no root")
+}
+
+fn make_tree<'a>(hm: &'a HashMap<&str, Definition>) -> Result<Tree<Entry<'a>>> {
+    let mut tree: Tree<Entry> = Tree::new();
+    let mut work = Vec::new();
+    let Some(root) = hm.get(&"root") else {
+        return Err(NoAnswer::new("there's no root monkey").into());
+    };
+    tree.set_root(root.op.clone());
+    for c in &root.children {
+        work.push((tree.root_id().unwrap(), *c));
+    }
+    while let Some(e) = work.pop() {
+        let Some(op) = hm.get(e.1) else {
+            return Err(NoAnswer::new(format!("there's no monkey named {}", e.1)).into());
+        };
+        // A monkey waiting on itself would grow the tree forever.
+        let mut up = tree.get(e.0);
+        while let Some(p) = up {
+            if p.data().name == e.1 {
+                return Err(NoAnswer::new(format!("monkey {} waits on itself", e.1)).into());
+            }
+            up = p.parent();
+        }
+        let mut n = tree.get_mut(e.0).unwrap();
+        match &op.op.value {
+            Item::Value(v) => {
+                n.append(Entry {
+                    name: op.op.name,
+                    value: Item::Value(*v),
+                });
+            }
+            Item::Operation(o) => {
+                let id = n
+                    .append(Entry {
+                        name: op.op.name,
+                        value: Item::Operation(o.clone()),
+                    })
+                    .node_id();
+                // This pushes the children in reverse order. Account below when we do the op.
+                work.push((id, op.children[0]));
+                work.push((id, op.children[1]));
+            }
+        }
+    }
+    Ok(tree)
+}
+
+fn transform_tree(tree: &mut Tree<Entry>, part2: bool) -> Result<()> {
+    for n in tree
+        .root()
+        .ok_or_else(no_root)?
+        .traverse_post_order()
+        .map(|n| n.node_id())
+        .collect::<Vec<_>>()
+    {
+        if part2 {
+            let mut skip = false;
+            for c in tree.get(n).unwrap().children() {
+                if c.data().name == "humn" {
+                    let humn = c.node_id();
+                    let mut node = tree.get_mut(humn).unwrap();
+                    node.data().value = Item::Operation(Operation::Unknown);
+                    skip = true;
+                    break;
+                }
+                if let Item::Operation(_) = c.data().value {
+                    skip = true;
+                    break;
+                }
+            }
+            if skip {
+                continue;
+            }
+        }
+        let node = tree.get(n).unwrap();
+        // Values are end nodes so we don't care here.
+        let Item::Operation(op) = node.data().value.clone() else {
+            continue;
+        };
+        let unknown = || NoAnswer::new(format!("{} waits on humn", node.data().name));
+        let mut vals = Vec::new();
+        for c in node.children() {
+            match c.data().value {
+                Item::Value(v) => vals.push(v),
+                Item::Operation(_) => return Err(unknown().into()),
+            }
+        }
+        let new: i64 = match op {
+            Operation::Plus => vals.iter().sum(),
+            Operation::Minus => vals[1] - vals[0],
+            Operation::Multiply => vals[1] * vals[0],
+            Operation::Divide => match vals[1].checked_div(vals[0]) {
+                Some(v) => v,
+                None => {
+                    return Err(
+                        NoAnswer::new(format!("{} divides by zero", node.data().name)).into(),
+                    )
+                }
+            },
+            Operation::Unknown => return Err(unknown().into()),
+        };
+        let mut node = tree.get_mut(n).unwrap();
+        node.data().value = Item::Value(new);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 152);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 301);
+        Ok(())
+    }
+
+    #[test]
+    fn render_example() -> Result<()> {
+        let tree = render(&parse(EXAMPLE)?)?;
+        assert!(tree.contains(r#"name: "sjmn", value: Value(150)"#));
+        assert!(tree.contains(r#"name: "humn", value: Operation(Unknown)"#));
+        Ok(())
+    }
+
+    #[test]
+    fn bad_operation() -> Result<()> {
+        let err = parse("root: pppw ^ sjmn\n")
+            .unwrap_err()
+            .downcast::<ParseError>()?;
+        assert_eq!((err.line, err.column), (1, 12));
+        assert_eq!(err.found, "`^`");
+        Ok(())
+    }
+
+    #[test]
+    fn missing_monkeys() -> Result<()> {
+        let no_root = parse("humn: 5\n")?;
+        assert!(part1(&no_root).unwrap_err().is::<NoAnswer>());
+        let dangling = parse("root: humn + abcd\nhumn: 5\n")?;
+        assert!(part1(&dangling).unwrap_err().is::<NoAnswer>());
+        let cycle =
+            parse("root: aaaa + bbbb\naaaa: bbbb * cccc\nbbbb: aaaa + cccc\ncccc: 2\nhumn: 5\n")?;
+        assert!(part1(&cycle).unwrap_err().is::<NoAnswer>());
+        assert!(part2(&cycle).unwrap_err().is::<NoAnswer>());
+        let own_root = parse("root: root + humn\nhumn: 5\n")?;
+        assert!(part1(&own_root).unwrap_err().is::<NoAnswer>());
+        let no_humn = parse("root: abcd + efgh\nabcd: 4\nefgh: 0\n")?;
+        assert!(part2(&no_humn).unwrap_err().is::<NoAnswer>());
+        let zero = parse("root: abcd / efgh\nabcd: 4\nefgh: 0\n")?;
+        assert!(part1(&zero).unwrap_err().is::<NoAnswer>());
+        let times_zero =
+            parse("root: abcd + efgh\nabcd: humn * zero\nhumn: 1\nzero: 0\nefgh: 4\n")?;
+        assert!(part2(&times_zero).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+//! day22 benchmarks.
+aoc_common::benches!(day22, [part1, part2]);
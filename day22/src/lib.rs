@@ -0,0 +1,834 @@
+//! day22 advent 2022
+use aoc_common::{lines, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use strum_macros::Display;
+
+/// One tile of the map.
+#[derive(Clone, Debug, Display, Eq, Hash, PartialEq)]
+pub enum Forest {
+    /// Off the map.
+    Void,
+    /// Open tile.
+    Path,
+    /// Solid wall.
+    Wall,
+}
+
+/// One instruction of the path.
+#[derive(Clone, Debug, Display, Eq, Hash, PartialEq)]
+pub enum Move {
+    /// Move forward this many tiles.
+    Steps(usize),
+    /// Turn 90 degrees counterclockwise.
+    Left,
+    /// Turn 90 degrees clockwise.
+    Right,
+}
+
+/// Which way the walker faces, clockwise from east so turning is a step
+/// through [`FACINGS`]. The discriminant is what facing adds to the password.
+#[derive(Clone, Copy, Debug, Display, Eq, Hash, PartialEq)]
+enum Facing {
+    East,
+    South,
+    West,
+    North,
+}
+
+const FACINGS: [Facing; 4] = [Facing::East, Facing::South, Facing::West, Facing::North];
+
+impl Facing {
+    fn right(self) -> Self {
+        FACINGS[(self as usize + 1) % 4]
+    }
+
+    fn left(self) -> Self {
+        FACINGS[(self as usize + 3) % 4]
+    }
+
+    // How the puzzle draws someone facing this way.
+    fn arrow(self) -> char {
+        match self {
+            Facing::East => '>',
+            Facing::South => 'v',
+            Facing::West => '<',
+            Facing::North => '^',
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Location(usize, usize);
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({},{})", self.0, self.1)
+    }
+}
+
+impl Location {
+    // One step towards `facing`, if that's not off the top or left.
+    fn step(self, facing: Facing) -> Option<Self> {
+        Some(match facing {
+            Facing::East => Location(self.0 + 1, self.1),
+            Facing::South => Location(self.0, self.1 + 1),
+            Facing::West => Location(self.0.checked_sub(1)?, self.1),
+            Facing::North => Location(self.0, self.1.checked_sub(1)?),
+        })
+    }
+}
+
+// The tile at `at`. Anything off the map is void.
+fn tile(forest: &[Vec<Forest>], at: Location) -> Forest {
+    forest
+        .get(at.1)
+        .and_then(|row| row.get(at.0))
+        .cloned()
+        .unwrap_or(Forest::Void)
+}
+
+// A direction in 3D once the net is folded up. Always a unit vector along
+// one axis.
+type Vec3 = [i64; 3];
+
+fn neg(v: Vec3) -> Vec3 {
+    [-v[0], -v[1], -v[2]]
+}
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(v: Vec3, k: i64) -> Vec3 {
+    [v[0] * k, v[1] * k, v[2] * k]
+}
+
+fn dot(a: Vec3, b: Vec3) -> i64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// One face of the net and which way it points once folded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Face {
+    /// Position in the grid of faces, so map coordinates over the face size.
+    cell: Location,
+    /// Outward normal.
+    normal: Vec3,
+    /// The direction map x increases along.
+    right: Vec3,
+    /// The direction map y increases along.
+    down: Vec3,
+}
+
+impl Face {
+    // The 3D direction of walking towards `facing` on this face.
+    fn direction(&self, facing: Facing) -> Vec3 {
+        match facing {
+            Facing::East => self.right,
+            Facing::South => self.down,
+            Facing::West => neg(self.right),
+            Facing::North => neg(self.down),
+        }
+    }
+}
+
+/// The map split into its six faces and folded into a cube.
+///
+/// Folding starts from the first face with it lying flat and walks the net.
+/// Crossing an edge in the net tips the next face over that edge, so its
+/// normal becomes the direction that was walked and walking on continues
+/// away from the old normal. Every transition off the map then falls out of
+/// where the faces end up in 3D rather than being written down per layout.
+struct Net {
+    /// Width and height of each face.
+    size: usize,
+    faces: Vec<Face>,
+}
+
+impl Net {
+    fn new(forest: &[Vec<Forest>]) -> Result<Self> {
+        let tiles = forest
+            .iter()
+            .flatten()
+            .filter(|f| **f != Forest::Void)
+            .count();
+        let size = (1..=tiles)
+            .take_while(|n| 6 * n * n <= tiles)
+            .last()
+            .unwrap_or(0);
+        if size == 0 || 6 * size * size != tiles {
+            return Err(NoAnswer::new(format!(
+                "{tiles} tiles can't make the 6 square faces of a cube"
+            ))
+            .into());
+        }
+        let width = forest.first().map_or(0, Vec::len).div_ceil(size);
+        let height = forest.len().div_ceil(size);
+
+        // With exactly 6 faces worth of tiles, 6 full blocks is all of them.
+        let mut cells = Vec::new();
+        for fy in 0..height {
+            for fx in 0..width {
+                let corner = Location(fx * size, fy * size);
+                let open = (0..size * size)
+                    .filter(|i| {
+                        let at = Location(corner.0 + i % size, corner.1 + i / size);
+                        tile(forest, at) != Forest::Void
+                    })
+                    .count();
+                if open == size * size {
+                    cells.push(Location(fx, fy));
+                } else if open != 0 {
+                    return Err(NoAnswer::new(format!(
+                        "the map doesn't split into {size}x{size} faces at {corner}"
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        let mut faces = vec![None; cells.len()];
+        faces[0] = Some(Face {
+            cell: cells[0],
+            normal: [0, 0, 1],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+        });
+        let mut work = vec![0];
+        while let Some(i) = work.pop() {
+            let Some(f) = faces[i].clone() else {
+                continue;
+            };
+            for facing in FACINGS {
+                let Some(next) = f.cell.step(facing) else {
+                    continue;
+                };
+                let Some(j) = cells.iter().position(|c| *c == next) else {
+                    continue;
+                };
+                if faces[j].is_some() {
+                    continue;
+                }
+                let (normal, right, down) = match facing {
+                    Facing::East => (f.right, neg(f.normal), f.down),
+                    Facing::West => (neg(f.right), f.normal, f.down),
+                    Facing::South => (f.down, f.right, neg(f.normal)),
+                    Facing::North => (neg(f.down), f.right, f.normal),
+                };
+                faces[j] = Some(Face {
+                    cell: next,
+                    normal,
+                    right,
+                    down,
+                });
+                work.push(j);
+            }
+        }
+        let Some(faces) = faces.into_iter().collect::<Option<Vec<_>>>() else {
+            return Err(NoAnswer::new("the faces aren't all joined up").into());
+        };
+        for (i, f) in faces.iter().enumerate() {
+            if faces[..i].iter().any(|g| g.normal == f.normal) {
+                return Err(NoAnswer::new(format!(
+                    "the face at {} folds onto another one so this isn't a cube net",
+                    Location(f.cell.0 * size, f.cell.1 * size)
+                ))
+                .into());
+            }
+        }
+        Ok(Self { size, faces })
+    }
+
+    // The face `at` is on.
+    fn face(&self, at: Location) -> &Face {
+        &self.faces[self.number(at)]
+    }
+
+    // Index of the face `at` is on. Faces are numbered across then down
+    // the map.
+    fn number(&self, at: Location) -> usize {
+        let cell = Location(at.0 / self.size, at.1 / self.size);
+        // Walking only ever lands on tiles and every tile is on a face.
+        self.faces.iter().position(|f| f.cell == cell).unwrap()
+    }
+
+    // Whether there's a face at `cell` in the grid of faces.
+    fn has(&self, cell: Location) -> bool {
+        self.faces.iter().any(|f| f.cell == cell)
+    }
+
+    // Off the edge of a face folded into a cube: over onto the face the
+    // walk was heading towards.
+    //
+    // Tiles are placed in 3D at twice scale so their centers are whole
+    // numbers and the cube runs from -size to size. Stepping over an edge
+    // moves one along the way we're heading and one back in from the old
+    // face.
+    fn wrap_cube(&self, at: Location, facing: Facing) -> Result<(Location, Facing)> {
+        let face = self.face(at);
+        let n = i64::try_from(self.size)?;
+        let x = i64::try_from(at.0 % self.size)?;
+        let y = i64::try_from(at.1 % self.size)?;
+        let heading = face.direction(facing);
+        let p = add(
+            add(scale(face.normal, n), scale(face.right, 2 * x + 1 - n)),
+            scale(face.down, 2 * y + 1 - n),
+        );
+        let p = add(add(p, heading), neg(face.normal));
+
+        // The normals cover every axis both ways so both of these exist.
+        let next = self.faces.iter().find(|f| f.normal == heading).unwrap();
+        let facing = FACINGS
+            .into_iter()
+            .find(|f| next.direction(*f) == neg(face.normal))
+            .unwrap();
+        let x = usize::try_from((dot(p, next.right) + n - 1) / 2)?;
+        let y = usize::try_from((dot(p, next.down) + n - 1) / 2)?;
+        Ok((
+            Location(next.cell.0 * self.size + x, next.cell.1 * self.size + y),
+            facing,
+        ))
+    }
+}
+
+/// Wrapping for the flat map: off one end of a row or column and back on
+/// at the other. Works for any shape of map, cube net or not.
+struct Flat {
+    /// First and last tile on each row.
+    rows: Vec<(usize, usize)>,
+    /// First and last tile in each column.
+    cols: Vec<(usize, usize)>,
+}
+
+impl Flat {
+    fn new(forest: &[Vec<Forest>]) -> Self {
+        // Rows and columns with no tiles at all can't be walked onto so
+        // what they hold doesn't matter.
+        let ends = |tiles: Vec<Forest>| {
+            let first = tiles.iter().position(|f| *f != Forest::Void);
+            let last = tiles.iter().rposition(|f| *f != Forest::Void);
+            first.zip(last).unwrap_or((0, 0))
+        };
+        let width = forest.first().map_or(0, Vec::len);
+        Self {
+            rows: forest.iter().map(|row| ends(row.clone())).collect(),
+            cols: (0..width)
+                .map(|x| ends(forest.iter().map(|row| row[x].clone()).collect()))
+                .collect(),
+        }
+    }
+
+    fn wrap(&self, at: Location, facing: Facing) -> (Location, Facing) {
+        let at = match facing {
+            Facing::East => Location(self.rows[at.1].0, at.1),
+            Facing::South => Location(at.0, self.cols[at.0].0),
+            Facing::West => Location(self.rows[at.1].1, at.1),
+            Facing::North => Location(at.0, self.cols[at.0].1),
+        };
+        (at, facing)
+    }
+}
+
+// Where one step towards `facing` from `at` ends up and which way it faces
+// after, going through `wrap` off the edge of the map. Doesn't care about
+// walls.
+fn step(
+    forest: &[Vec<Forest>],
+    at: Location,
+    facing: Facing,
+    wrap: &impl Fn(Location, Facing) -> Result<(Location, Facing)>,
+) -> Result<(Location, Facing)> {
+    match at.step(facing) {
+        Some(next) if tile(forest, next) != Forest::Void => Ok((next, facing)),
+        _ => wrap(at, facing),
+    }
+}
+
+// One tile stood on during a walk.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Visit {
+    at: Location,
+    /// Which way it was facing on leaving (or at the end).
+    facing: Facing,
+    /// Where it came from if it got here by going off the edge.
+    from: Option<(Location, Facing)>,
+}
+
+// Walk `moves` from the leftmost open tile on the top row, returning where
+// that ends and which way it faces. `visit` sees the start, every step and
+// every turn.
+fn walk(
+    forest: &[Vec<Forest>],
+    moves: &[Move],
+    wrap: impl Fn(Location, Facing) -> Result<(Location, Facing)>,
+    mut visit: impl FnMut(Visit),
+) -> Result<(Location, Facing)> {
+    let start = forest
+        .first()
+        .and_then(|row| row.iter().position(|f| *f == Forest::Path));
+    let Some(x) = start else {
+        return Err(NoAnswer::new("there's no open tile on the top row to start from").into());
+    };
+    let mut at = Location(x, 0);
+    let mut facing = Facing::East;
+    visit(Visit {
+        at,
+        facing,
+        from: None,
+    });
+    for m in moves {
+        match m {
+            Move::Steps(s) => {
+                for _ in 0..*s {
+                    let (next, turned) = step(forest, at, facing, &wrap)?;
+                    if tile(forest, next) == Forest::Wall {
+                        break;
+                    }
+                    // Stepping normally always lands next door.
+                    let from = (at.step(facing) != Some(next)).then_some((at, facing));
+                    (at, facing) = (next, turned);
+                    visit(Visit { at, facing, from });
+                }
+            }
+            Move::Left => {
+                facing = facing.left();
+                visit(Visit {
+                    at,
+                    facing,
+                    from: None,
+                });
+            }
+            Move::Right => {
+                facing = facing.right();
+                visit(Visit {
+                    at,
+                    facing,
+                    from: None,
+                });
+            }
+        }
+    }
+    Ok((at, facing))
+}
+
+/// The map and the path to walk over it.
+#[derive(Debug)]
+pub struct Input {
+    /// The map indexed by `[y][x]`. Every row is padded to the same length.
+    pub forest: Vec<Vec<Forest>>,
+    /// The path.
+    pub moves: Vec<Move>,
+}
+
+/// Parse the map and the path.
+///
+/// # Errors
+/// On an unknown tile or path character.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut forest = Vec::new();
+    let mut forest_done = false;
+    let mut moves = Vec::new();
+    let mut max = 0;
+    for (line_num, line) in lines(input) {
+        if line.is_empty() {
+            forest_done = true;
+            continue;
+        }
+        if forest_done {
+            let mut start = 0;
+            for (pos, c) in line.bytes().enumerate() {
+                match c {
+                    b'0' | b'1' | b'2' | b'3' | b'4' | b'5' | b'6' | b'7' | b'8' | b'9' => {}
+                    b'R' | b'L' => {
+                        if start == pos {
+                            return Err(ParseError::new(
+                                line_num,
+                                line,
+                                &line[pos..=pos],
+                                "a step count",
+                            )
+                            .into());
+                        }
+                        let m =
+                            ParseError::parse(line_num, line, &line[start..pos], "a step count")?;
+                        start = pos + 1;
+                        moves.push(Move::Steps(m));
+                        if c == b'R' {
+                            moves.push(Move::Right);
+                        } else {
+                            moves.push(Move::Left);
+                        }
+                    }
+                    _ => {
+                        return Err(ParseError::new(
+                            line_num,
+                            line,
+                            line.get(pos..=pos).unwrap_or(&line[pos..]),
+                            "a step count, `R` or `L`",
+                        )
+                        .into());
+                    }
+                }
+            }
+            // Get any trailing number.
+            if start != line.len() {
+                let m = ParseError::parse(line_num, line, &line[start..], "a step count")?;
+                moves.push(Move::Steps(m));
+            }
+            break;
+        }
+        let mut l = vec![Forest::Void; line.len()];
+        if line.len() > max {
+            max = line.len();
+        }
+        for (pos, c) in line.bytes().enumerate() {
+            match c {
+                b' ' => {}
+                b'.' => l[pos] = Forest::Path,
+                b'#' => l[pos] = Forest::Wall,
+                _ => {
+                    return Err(ParseError::new(
+                        line_num,
+                        line,
+                        line.get(pos..=pos).unwrap_or(&line[pos..]),
+                        "one of ` `, `.` or `#`",
+                    )
+                    .into());
+                }
+            }
+        }
+        forest.push(l);
+    }
+
+    // Fixup the grid. It may be asymetric now but we want each row to be the same length
+    // (otherwise the logic below can be out of bounds). So for each row fill in anything
+    // missing with a void.
+    for f in &mut forest {
+        for _ in f.len()..max {
+            f.push(Forest::Void);
+        }
+    }
+
+    Ok(Input { forest, moves })
+}
+
+/// Final password walking the flat map.
+///
+/// # Errors
+/// See [`password`].
+pub fn part1(input: &Input) -> Result<usize> {
+    password(input, false)
+}
+
+/// Final password walking the map folded into a cube.
+///
+/// # Errors
+/// See [`password`].
+pub fn part2(input: &Input) -> Result<usize> {
+    password(input, true)
+}
+
+/// Final password after walking the path. `cube` folds the map into a cube
+/// instead of wrapping flat.
+///
+/// Flat, running off one end of a row or column comes back on at the
+/// other. As a cube the face size and layout come from the map so any of
+/// the 11 cube nets works at any size.
+///
+/// # Errors
+/// If there's no open tile on the top row to start from or, for a cube,
+/// the map doesn't split into 6 square faces that fold into one.
+pub fn password(input: &Input, cube: bool) -> Result<usize> {
+    Ok(run(input, cube, |_| {})?.1)
+}
+
+/// Walk the path like [`password`] keeping every tile stood on so it can
+/// be drawn.
+///
+/// # Errors
+/// As for [`password`].
+pub fn replay(input: &Input, cube: bool) -> Result<Replay<'_>> {
+    let mut trail = Vec::new();
+    let (net, password) = run(input, cube, |v| trail.push(v))?;
+    Ok(Replay {
+        forest: &input.forest,
+        // Face boundaries are worth drawing on any map that is a net even
+        // when walking it flat.
+        net: net.or_else(|| Net::new(&input.forest).ok()),
+        trail,
+        password,
+    })
+}
+
+// Walk the path returning the net if it was folded and the password.
+fn run(input: &Input, cube: bool, visit: impl FnMut(Visit)) -> Result<(Option<Net>, usize)> {
+    let forest = &input.forest;
+    let (net, (at, facing)) = if cube {
+        let net = Net::new(forest)?;
+        let end = walk(
+            forest,
+            &input.moves,
+            |at, facing| net.wrap_cube(at, facing),
+            visit,
+        )?;
+        (Some(net), end)
+    } else {
+        let flat = Flat::new(forest);
+        let end = walk(
+            forest,
+            &input.moves,
+            |at, facing| Ok(flat.wrap(at, facing)),
+            visit,
+        )?;
+        (None, end)
+    };
+    Ok((net, 1000 * (at.1 + 1) + 4 * (at.0 + 1) + facing as usize))
+}
+
+/// One walk over the map, drawn the way the puzzle does.
+///
+/// Every tile stood on shows which way it was last faced as `>`, `v`, `<`
+/// or `^`. If the map is a cube net the faces are boxed in with `|`, `-`
+/// and `+`. Below that every trip off an edge is listed with where it
+/// left and arrived (1 based rows and columns like the password) and, on a
+/// net, which faces it joined. Faces are numbered across then down.
+pub struct Replay<'a> {
+    forest: &'a [Vec<Forest>],
+    net: Option<Net>,
+    trail: Vec<Visit>,
+    password: usize,
+}
+
+impl Replay<'_> {
+    /// The password at the end of the walk.
+    #[must_use]
+    pub fn password(&self) -> usize {
+        self.password
+    }
+
+    // Row `y` of the board with face boundaries in if there's a net.
+    fn row(&self, board: &[Vec<char>], y: usize) -> String {
+        let mut out = String::new();
+        for (x, c) in board[y].iter().enumerate() {
+            if let Some(net) = &self.net {
+                if x > 0 && x % net.size == 0 {
+                    let (fx, fy) = (x / net.size, y / net.size);
+                    let edge = net.has(Location(fx - 1, fy)) || net.has(Location(fx, fy));
+                    out.push(if edge { '|' } else { ' ' });
+                }
+            }
+            out.push(*c);
+        }
+        String::from(out.trim_end())
+    }
+
+    // The boundary above face row `fy`.
+    fn boundary(net: &Net, width: usize, fy: usize) -> String {
+        let mut out = String::new();
+        for x in 0..width {
+            let fx = x / net.size;
+            if x > 0 && x % net.size == 0 {
+                let corner = [(fx - 1, fy - 1), (fx, fy - 1), (fx - 1, fy), (fx, fy)]
+                    .iter()
+                    .any(|c| net.has(Location(c.0, c.1)));
+                out.push(if corner { '+' } else { ' ' });
+            }
+            let edge = net.has(Location(fx, fy - 1)) || net.has(Location(fx, fy));
+            out.push(if edge { '-' } else { ' ' });
+        }
+        String::from(out.trim_end())
+    }
+}
+
+impl std::fmt::Display for Replay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut board = self
+            .forest
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|t| match t {
+                        Forest::Void => ' ',
+                        Forest::Path => '.',
+                        Forest::Wall => '#',
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for v in &self.trail {
+            board[v.at.1][v.at.0] = v.facing.arrow();
+        }
+        let width = board.first().map_or(0, Vec::len);
+        for y in 0..board.len() {
+            if let Some(net) = &self.net {
+                if y > 0 && y % net.size == 0 {
+                    writeln!(f, "{}", Self::boundary(net, width, y / net.size))?;
+                }
+            }
+            writeln!(f, "{}", self.row(&board, y))?;
+        }
+        let wraps = self
+            .trail
+            .iter()
+            .filter_map(|v| v.from.map(|from| (from, (v.at, v.facing))));
+        for (i, (from, to)) in wraps.enumerate() {
+            write!(
+                f,
+                "\n{}: row {} column {} {} to row {} column {} {}",
+                i + 1,
+                from.0 .1 + 1,
+                from.0 .0 + 1,
+                from.1.arrow(),
+                to.0 .1 + 1,
+                to.0 .0 + 1,
+                to.1.arrow()
+            )?;
+            if let Some(net) = &self.net {
+                write!(
+                    f,
+                    " (face {} to {})",
+                    net.number(from.0) + 1,
+                    net.number(to.0) + 1
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    // Every cube net drawn one character per face.
+    const NETS: [&str; 11] = [
+        "#...\n####\n#...",
+        "#...\n####\n.#..",
+        "#...\n####\n..#.",
+        "#...\n####\n...#",
+        ".#..\n####\n.#..",
+        ".#..\n####\n..#.",
+        "##..\n.###\n.#..",
+        "##..\n.###\n..#.",
+        "##..\n.###\n...#",
+        "##..\n.##.\n..##",
+        "###..\n..###",
+    ];
+
+    // `net` drawn with open `size` wide faces.
+    fn map(net: &str, size: usize) -> String {
+        let mut map = String::new();
+        for row in net.lines() {
+            let line = row
+                .chars()
+                .map(|c| if c == '#' { "." } else { " " }.repeat(size))
+                .collect::<String>();
+            for _ in 0..size {
+                map.push_str(line.trim_end());
+                map.push('\n');
+            }
+        }
+        map + "\n0\n"
+    }
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 6032);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 5031);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_path() -> Result<()> {
+        let err = parse("..#\n\n10R5X3\n")
+            .unwrap_err()
+            .downcast::<ParseError>()?;
+        assert_eq!((err.line, err.column), (3, 5));
+        assert_eq!(err.expected, "a step count, `R` or `L`");
+        Ok(())
+    }
+
+    // Going once round the cube in a straight line from anywhere comes
+    // back to the same tile facing the same way.
+    #[test]
+    fn every_net_folds() -> Result<()> {
+        for net in NETS {
+            for size in 1..=3 {
+                let input = parse(&map(net, size))?;
+                let cube = Net::new(&input.forest)?;
+                let wrap = |at, facing| cube.wrap_cube(at, facing);
+                for y in 0..input.forest.len() {
+                    for x in 0..input.forest[y].len() {
+                        let start = Location(x, y);
+                        if tile(&input.forest, start) == Forest::Void {
+                            continue;
+                        }
+                        for facing in FACINGS {
+                            let mut at = (start, facing);
+                            for _ in 0..4 * size {
+                                at = step(&input.forest, at.0, at.1, &wrap)?;
+                            }
+                            assert_eq!(at, (start, facing), "{net:?} at size {size}");
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn replay_example() -> Result<()> {
+        let input = parse(EXAMPLE)?;
+        let r = replay(&input, false)?;
+        assert_eq!(r.password(), 6032);
+        let expected = [
+            "         |>>v#|",
+            "         |.#v.|",
+            "         |#.v.|",
+            "         |..v.|",
+            "----+----+----+",
+            "...#|...v|..v#|",
+            ">>>v|...>|#.>>|",
+            "..#v|...#|....|",
+            "...>|>>>v|..#.|",
+            "----+----+----+----",
+            "         |...#|....",
+            "         |....|.#..",
+            "         |.#..|....",
+            "         |....|..#.",
+            "",
+            "1: row 6 column 12 > to row 6 column 1 > (face 4 to 2)",
+            "2: row 8 column 8 v to row 5 column 8 v (face 3 to 3)",
+        ];
+        assert_eq!(r.to_string(), expected.join("\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn jagged_flat() -> Result<()> {
+        let input = parse("...\n ....\n  .#\n\n5R3\n")?;
+        assert_eq!(part1(&input)?, 1013);
+        let r = replay(&input, false)?;
+        assert!(r.to_string().starts_with(">>v\n .v..\n  v#\n"));
+        assert!(part2(&input).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+
+    #[test]
+    fn not_a_cube() -> Result<()> {
+        let walled = parse(&EXAMPLE.replacen("...#", "####", 1))?;
+        assert!(part1(&walled).unwrap_err().is::<NoAnswer>());
+        for map in ["..\n\n1", "......\n\n1", "..\n...\n.\n\n1"] {
+            assert!(part2(&parse(map)?).unwrap_err().is::<NoAnswer>(), "{map}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+//! day23 benchmarks.
+aoc_common::benches!(day23, [part1, part2]);
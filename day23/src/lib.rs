@@ -0,0 +1,307 @@
+//! day23 advent 2022
+use aoc_common::{lines, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use strum_macros::Display;
+
+#[derive(Clone, Debug, Display, Eq, Hash, PartialEq)]
+enum Facing {
+    North,
+    South,
+    East,
+    West,
+}
+
+/// An x,y position of an elf.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Location(pub i64, pub i64);
+
+impl Ord for Location {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // We want to sort by row, then column order.
+        // If we derive we get column/row instead unless we reverse
+        // the tuple.
+        let o = self.1.cmp(&other.1);
+        match o {
+            std::cmp::Ordering::Equal => self.0.cmp(&other.0),
+            std::cmp::Ordering::Greater | std::cmp::Ordering::Less => o,
+        }
+    }
+}
+
+impl PartialOrd for Location {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({},{})", self.0, self.1)
+    }
+}
+
+/// Where the elves are.
+pub type Input = HashSet<Location>;
+
+const RULES: [Facing; 4] = [Facing::North, Facing::South, Facing::West, Facing::East];
+
+/// Parse the elf positions.
+///
+/// # Errors
+/// On an unknown character or if a coordinate doesn't fit.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut map = HashSet::new();
+    for (line_num, line) in lines(input) {
+        for (pos, c) in line.as_bytes().iter().enumerate() {
+            match c {
+                b'#' => {
+                    map.insert(Location(pos.try_into()?, line_num.try_into()?));
+                }
+                b'.' => {}
+                _ => {
+                    return Err(ParseError::new(
+                        line_num,
+                        line,
+                        line.get(pos..=pos).unwrap_or(&line[pos..]),
+                        "`#` or `.`",
+                    )
+                    .into());
+                }
+            }
+        }
+    }
+    Ok(map)
+}
+
+/// Empty ground in the bounding rectangle after 10 rounds.
+///
+/// # Errors
+/// See [`empty_ground`].
+pub fn part1(map: &Input) -> Result<i64> {
+    empty_ground(map, 10)
+}
+
+/// First round where no elf moves.
+///
+/// # Errors
+/// None, it keeps going until no elf moves.
+pub fn part2(map: &Input) -> Result<usize> {
+    let mut map = map.clone();
+    Ok(run_rounds(usize::MAX, &mut map, &RULES))
+}
+
+/// Empty ground in the bounding rectangle of the elves after `rounds` rounds.
+///
+/// # Errors
+/// If there are no elves to bound or the elf count doesn't fit.
+pub fn empty_ground(map: &Input, rounds: usize) -> Result<i64> {
+    if map.is_empty() {
+        return Err(NoAnswer::new("there are no elves to draw a rectangle around").into());
+    }
+    let mut map = map.clone();
+    run_rounds(rounds, &mut map, &RULES);
+    let (min_x, max_x, min_y, max_y) = bounds(&map);
+    let len: i64 = map.len().try_into()?;
+    Ok(((max_x - min_x + 1) * (max_y - min_y + 1)) - len)
+}
+
+/// Draw the elves the way the input does, cropped to the rectangle around
+/// them. No elves draws nothing.
+#[must_use]
+pub fn render(map: &Input) -> String {
+    let mut out = String::new();
+    if map.is_empty() {
+        return out;
+    }
+    let (min_x, max_x, min_y, max_y) = bounds(map);
+    for y in min_y..=max_y {
+        for x in min_x..=max_x {
+            if map.contains(&Location(x, y)) {
+                out.push('#');
+            } else {
+                out.push('.');
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// The min x, max x, min y and max y of the elves.
+fn bounds(map: &HashSet<Location>) -> (i64, i64, i64, i64) {
+    let (mut min_x, mut max_x, mut min_y, mut max_y) = (i64::MAX, i64::MIN, i64::MAX, i64::MIN);
+    for m in map {
+        if m.0 < min_x {
+            min_x = m.0;
+        }
+        if m.0 > max_x {
+            max_x = m.0;
+        }
+        if m.1 < min_y {
+            min_y = m.1;
+        }
+        if m.1 > max_y {
+            max_y = m.1;
+        }
+    }
+    (min_x, max_x, min_y, max_y)
+}
+
+#[allow(clippy::too_many_lines)]
+fn run_rounds(rounds: usize, map: &mut HashSet<Location>, rules: &[Facing]) -> usize {
+    let mut start_rule = 0;
+    for r in 0..rounds {
+        let mut proposed = HashMap::new();
+        for l in map.iter() {
+            let mut has_neighbors = false;
+            for tests in [
+                &Location(l.0 - 1, l.1),     // W
+                &Location(l.0 + 1, l.1),     // E
+                &Location(l.0, l.1 - 1),     // N
+                &Location(l.0, l.1 + 1),     // S
+                &Location(l.0 - 1, l.1 - 1), // NW
+                &Location(l.0 - 1, l.1 + 1), // SW
+                &Location(l.0 + 1, l.1 - 1), // NE
+                &Location(l.0 + 1, l.1 + 1), // SE
+            ] {
+                if map.contains(tests) {
+                    has_neighbors = true;
+                    break;
+                }
+            }
+            // Don't move if you're alone.
+            if !has_neighbors {
+                continue;
+            }
+            for r in 0..rules.len() {
+                let i = (start_rule + r) % rules.len();
+                match rules.get(i).unwrap() {
+                    Facing::North => {
+                        if test_dir(
+                            map,
+                            l,
+                            [
+                                &Location(l.0, l.1 - 1),
+                                &Location(l.0 - 1, l.1 - 1),
+                                &Location(l.0 + 1, l.1 - 1),
+                            ],
+                            Location(l.0, l.1 - 1),
+                            &mut proposed,
+                        ) {
+                            break;
+                        }
+                    }
+                    Facing::South => {
+                        if test_dir(
+                            map,
+                            l,
+                            [
+                                &Location(l.0, l.1 + 1),
+                                &Location(l.0 - 1, l.1 + 1),
+                                &Location(l.0 + 1, l.1 + 1),
+                            ],
+                            Location(l.0, l.1 + 1),
+                            &mut proposed,
+                        ) {
+                            break;
+                        }
+                    }
+                    Facing::West => {
+                        if test_dir(
+                            map,
+                            l,
+                            [
+                                &Location(l.0 - 1, l.1),
+                                &Location(l.0 - 1, l.1 - 1),
+                                &Location(l.0 - 1, l.1 + 1),
+                            ],
+                            Location(l.0 - 1, l.1),
+                            &mut proposed,
+                        ) {
+                            break;
+                        }
+                    }
+                    Facing::East => {
+                        if test_dir(
+                            map,
+                            l,
+                            [
+                                &Location(l.0 + 1, l.1),
+                                &Location(l.0 + 1, l.1 - 1),
+                                &Location(l.0 + 1, l.1 + 1),
+                            ],
+                            Location(l.0 + 1, l.1),
+                            &mut proposed,
+                        ) {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        if proposed.is_empty() {
+            return r + 1;
+        }
+        for p in &proposed {
+            if p.1.len() == 1 {
+                map.remove(&p.1[0]);
+                map.insert(p.0.clone());
+            }
+        }
+        start_rule += 1;
+        start_rule %= rules.len();
+    }
+    rounds
+}
+
+fn test_dir(
+    map: &HashSet<Location>,
+    l: &Location,
+    tests: [&Location; 3],
+    insert: Location,
+    proposed: &mut HashMap<Location, Vec<Location>>,
+) -> bool {
+    if !map.contains(tests[0]) && !map.contains(tests[1]) && !map.contains(tests[2]) {
+        proposed
+            .entry(insert)
+            .and_modify(|v| v.push(l.clone()))
+            .or_insert(vec![l.clone()]);
+        return true;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 110);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 20);
+        Ok(())
+    }
+
+    #[test]
+    fn render_example() -> Result<()> {
+        assert_eq!(render(&parse(EXAMPLE)?), EXAMPLE);
+        assert_eq!(render(&parse("....\n.#..\n..#.\n")?), "#.\n.#\n");
+        Ok(())
+    }
+
+    #[test]
+    fn no_elves() -> Result<()> {
+        assert!(part1(&parse("...\n")?).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+//! day24 benchmarks.
+// One of the slow days, keep the run time reasonable.
+aoc_common::benches!(day24, [part1, part2], sample_size = 10);
@@ -0,0 +1,369 @@
+//! day24 advent 2022
+use crate::{
+    Facing::{East, North, South, West},
+    Spot::{Blizzard, Expedition, Path, Wall},
+    Storm::{Multiple, Single},
+};
+use aoc_common::{NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use grid::{Grid, Location};
+use num::integer::lcm;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+use std::{fmt, iter};
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum Storm {
+    Single(Facing),
+    Multiple(Vec<Facing>),
+}
+
+impl fmt::Display for Storm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Single(facing) => write!(f, "{facing}"),
+            Multiple(v) => write!(f, "{}", v.len()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum Facing {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl fmt::Display for Facing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            North => write!(f, "^"),
+            South => write!(f, "v"),
+            East => write!(f, ">"),
+            West => write!(f, "<"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+enum Spot {
+    Wall,
+    #[default]
+    Path,
+    Blizzard(Storm),
+    Expedition,
+}
+
+impl fmt::Display for Spot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Wall => write!(f, "#"),
+            Spot::Path => write!(f, "."),
+            Blizzard(b) => write!(f, "{b}"),
+            Expedition => write!(f, "E"),
+        }
+    }
+}
+
+/// The valley with every blizzard state it cycles through.
+#[derive(Debug)]
+pub struct Input {
+    // One board per minute until the blizzards repeat.
+    boards: Vec<Grid<Spot>>,
+    /// The entrance on the top row.
+    pub start: Location,
+    /// The exit on the bottom row.
+    pub end: Location,
+}
+
+/// Parse the valley and precompute the blizzard cycle.
+///
+/// # Errors
+/// On an unknown character, rows of different widths, a valley smaller than
+/// 3x3, no opening in the top or bottom wall or if a coordinate doesn't fit.
+/// [`NoAnswer`] if a blizzard blows into a wall inside the valley.
+pub fn parse(input: &str) -> Result<Input> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let Some(width) = lines.first().map(|l| l.len()) else {
+        return Err(ParseError::end_of_input(0, "", "the valley").into());
+    };
+    if width < 3 {
+        return Err(ParseError::end_of_line(0, lines[0], "at least 3 columns").into());
+    }
+    if lines.len() < 3 {
+        let last = lines.len() - 1;
+        return Err(ParseError::end_of_input(last, lines[last], "at least 3 rows").into());
+    }
+    let mut grid = Grid::<Spot>::new(width, lines.len());
+
+    for (line_num, line) in lines.iter().enumerate() {
+        if line.len() > width {
+            return Err(ParseError::new(
+                line_num,
+                line,
+                line.get(width..).unwrap_or(line),
+                "end of line",
+            )
+            .into());
+        }
+        if line.len() < width {
+            return Err(ParseError::end_of_line(line_num, line, format!("{width} columns")).into());
+        }
+        for (pos, b) in line.as_bytes().iter().enumerate() {
+            let l = Location(pos.try_into()?, line_num.try_into()?);
+            match b {
+                b'#' => grid.add(&l, Wall),
+                b'.' => grid.add(&l, Path),
+                b'>' => grid.add(&l, Blizzard(Single(East))),
+                b'<' => grid.add(&l, Blizzard(Single(West))),
+                b'^' => grid.add(&l, Blizzard(Single(North))),
+                b'v' => grid.add(&l, Blizzard(Single(South))),
+                _ => {
+                    return Err(ParseError::new(
+                        line_num,
+                        line,
+                        line.get(pos..=pos).unwrap_or(&line[pos..]),
+                        "one of `#`, `.`, `>`, `<`, `^` or `v`",
+                    )
+                    .into());
+                }
+            }
+        }
+    }
+
+    // Find the first open hole and put the expedition there.
+    // Also find the end.
+    let opening = |row: usize| -> Result<Location> {
+        let y = row.try_into()?;
+        for x in 0..grid.width() {
+            let l = Location(x.try_into()?, y);
+            if *grid.get(&l) == Path {
+                return Ok(l);
+            }
+        }
+        Err(ParseError::new(row, lines[row], lines[row], "an opening `.`").into())
+    };
+    let exp = opening(0)?;
+    let end = opening(grid.height() - 1)?;
+
+    // Since the blizzard paths are symetric they simply repeat overall at the
+    // lcm(width,length) of that field.
+    let lcm = lcm(grid.height() - 2, grid.width() - 2);
+
+    // Use that lcm to generate all the boards we'd ever need for lookup
+    let mut boards = Vec::from([grid.clone()]);
+    for _ in 0..lcm - 1 {
+        move_blizzard(&mut grid)?;
+        boards.push(grid.clone());
+    }
+    Ok(Input {
+        boards,
+        start: exp,
+        end,
+    })
+}
+
+/// Fewest minutes to reach the exit.
+///
+/// # Errors
+/// If the blizzards never let the expedition through.
+pub fn part1(valley: &Input) -> Result<usize> {
+    bfs(&valley.boards, 0, &valley.start, &valley.end)
+}
+
+/// Fewest minutes to reach the exit, go back for the snacks and reach the
+/// exit again.
+///
+/// # Errors
+/// If the blizzards never let the expedition through.
+pub fn part2(valley: &Input) -> Result<usize> {
+    let len = bfs(&valley.boards, 0, &valley.start, &valley.end)?;
+    let len = bfs(&valley.boards, len, &valley.end, &valley.start)?;
+    bfs(&valley.boards, len, &valley.start, &valley.end)
+}
+
+fn bfs(boards: &[Grid<Spot>], len: usize, start: &Location, dest: &Location) -> Result<usize> {
+    let mut q = BinaryHeap::new();
+    let mut seen = HashSet::new();
+
+    // Use Reverse on the queue to turn this into a min based PQ. Otherwise
+    // max based makes Dijkstra sad :)
+    q.push(Reverse((len, start.distance(dest), start.clone())));
+
+    // We will backtrack but we can't repeat the same location+path length
+    // or else we'll loop. The boards repeat every lcm minutes so only the
+    // length mod that matters, which also means this runs out if there's
+    // no way through.
+    let lcm = boards.len();
+    seen.insert((len % lcm, start.clone()));
+
+    while let Some(e) = q.pop() {
+        let path_len = e.0 .0;
+        // Distance is e.1 but we don't need it to compute anything.
+        // It's in there so sorting for the queue uses it (a star).
+        let loc = e.0 .2;
+        if loc == *dest {
+            return Ok(path_len);
+        }
+        let new = path_len + 1;
+        let b = &boards[new % lcm];
+
+        // If we're at the edge then neighbors() will filter for us.
+        // Then all that remains is removing walls.
+        let neighbors = b
+            .neighbors(&loc)
+            .iter()
+            .chain(iter::once(&(loc.clone(), &Expedition))) // Add the current location in also as we might just wait
+            .filter(|x| *x.1 != Wall)
+            .cloned()
+            .collect::<Vec<(Location, &Spot)>>();
+
+        for t in &neighbors {
+            // Only attempt places that have paths as everything else
+            // is either a wall or blizzard.
+            if *b.get(&t.0) == Path && seen.insert((new % lcm, t.0.clone())) {
+                q.push(Reverse((new, t.0.distance(dest), t.0.clone())));
+            }
+        }
+    }
+
+    Err(NoAnswer::new(format!(
+        "the blizzards never clear a way from {start} to {dest}"
+    ))
+    .into())
+}
+
+fn move_blizzard(grid: &mut Grid<Spot>) -> Result<()> {
+    // We know this is an interior grid except the start and end holes
+    // but there are no blizzards going N/S on the first/last row so those will
+    // never get hit.
+    let mut newgrid = grid.clone();
+
+    // Create a copy we zero out and start moving into.
+    // We'll clone this back into grid when we're done.
+    for y in 0..grid.height() - 1 {
+        for x in 0..grid.width() - 1 {
+            let (x, y) = (x.try_into()?, y.try_into()?);
+            if *newgrid.get(&Location(x, y)) != Wall {
+                newgrid.add(&Location(x, y), Path);
+            }
+        }
+    }
+
+    for y in 0..grid.height() - 1 {
+        for x in 0..grid.width() - 1 {
+            let (x, y) = (x.try_into()?, y.try_into()?);
+            if let Blizzard(b) = grid.get(&Location(x, y)) {
+                match b {
+                    Single(s) => do_move(&mut newgrid, s, x, y)?,
+                    Multiple(m) => {
+                        for loc in m {
+                            do_move(&mut newgrid, loc, x, y)?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    *grid = newgrid.clone();
+    Ok(())
+}
+
+fn do_move(newgrid: &mut Grid<Spot>, s: &Facing, x: isize, y: isize) -> Result<()> {
+    let new = match s {
+        North => {
+            let mut new = Location(x, y - 1);
+            if *newgrid.get(&new) == Wall {
+                new = Location(x, (newgrid.height() - 2).try_into()?);
+            }
+            new
+        }
+        South => {
+            let mut new = Location(x, y + 1);
+            if *newgrid.get(&new) == Wall {
+                new = Location(x, 1);
+            }
+            new
+        }
+        East => {
+            let mut new = Location(x + 1, y);
+            if *newgrid.get(&new) == Wall {
+                new = Location(1, y);
+            }
+            new
+        }
+        West => {
+            let mut new = Location(x - 1, y);
+            if *newgrid.get(&new) == Wall {
+                new = Location((newgrid.width() - 2).try_into()?, y);
+            }
+            new
+        }
+    };
+    match newgrid.get_mut(&new) {
+        Wall | Expedition => {
+            return Err(NoAnswer::new(format!(
+                "the blizzard at {} blows into a wall",
+                Location(x, y)
+            ))
+            .into());
+        }
+        Spot::Path => newgrid.add(&new, Blizzard(Single(s.clone()))),
+
+        Blizzard(blz) => match blz {
+            Single(single) => {
+                // Have to move it here as inside of Vec::from it claims we're borrowing
+                // twice.
+                let new_facing = single.clone();
+                newgrid.add(&new, Blizzard(Multiple(Vec::from([new_facing, s.clone()]))));
+            }
+            Multiple(val) => val.push(s.clone()),
+        },
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 18);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 54);
+        Ok(())
+    }
+
+    #[test]
+    fn too_small() -> Result<()> {
+        let err = parse("#.#\n#.#\n").unwrap_err().downcast::<ParseError>()?;
+        assert_eq!(err.expected, "at least 3 rows");
+        let err = parse("#.#\n#.#\n###\n")
+            .unwrap_err()
+            .downcast::<ParseError>()?;
+        assert_eq!((err.line, err.expected.as_str()), (3, "an opening `.`"));
+        Ok(())
+    }
+
+    #[test]
+    fn no_way_through() -> Result<()> {
+        let valley = parse("#.###\n#.#.#\n###.#\n")?;
+        assert!(part1(&valley).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+
+    #[test]
+    fn wall_inside() {
+        let err = parse("#.####\n#<..##\n####.#\n").unwrap_err();
+        assert!(err.is::<NoAnswer>());
+    }
+}
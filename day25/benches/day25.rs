@@ -0,0 +1,2 @@
+//! day25 benchmarks.
+aoc_common::benches!(day25, [part1]);
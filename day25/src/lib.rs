@@ -0,0 +1,146 @@
+//! day25 advent 2022
+use aoc_common::{lines, ParseError};
+use color_eyre::eyre::{eyre, Result};
+use num::pow;
+use std::fmt::Write;
+
+/// The fuel requirements decoded from SNAFU.
+pub type Input = Vec<usize>;
+
+/// Parse the SNAFU numbers, one per line.
+///
+/// # Errors
+/// On a character that isn't a SNAFU digit.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut nums = Vec::new();
+    for (line_num, line) in lines(input) {
+        if let Some(pos) = line.find(|c| !"012-=".contains(c)) {
+            return Err(ParseError::new(
+                line_num,
+                line,
+                line.get(pos..=pos).unwrap_or(&line[pos..]),
+                "a SNAFU digit",
+            )
+            .into());
+        }
+        nums.push(snafu(line)?);
+    }
+    Ok(nums)
+}
+
+/// The sum of the fuel requirements as a SNAFU number. There's no part 2.
+///
+/// # Errors
+/// See [`convert`].
+pub fn part1(nums: &Input) -> Result<String> {
+    #[allow(
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation
+    )]
+    let sum = nums.iter().sum::<usize>() as f64;
+    convert(sum)
+}
+
+/// Decode a SNAFU number.
+///
+/// # Errors
+/// On a character that isn't a SNAFU digit or a negative number.
+pub fn snafu(inp: &str) -> Result<usize> {
+    // Signed since a trailing = or - goes negative before the higher digits
+    // bring it back up.
+    let mut num: i64 = 0;
+    for (pos, i) in inp.as_bytes().iter().rev().enumerate() {
+        let p: i64 = pow(5, pos);
+        match i {
+            b'1' => num += p,
+            b'2' => num += 2 * p,
+            b'=' => num -= 2 * p,
+            b'-' => num -= p,
+            b'0' => {}
+            _ => {
+                return Err(eyre!("Invalid line {inp}"));
+            }
+        }
+    }
+    Ok(num.try_into()?)
+}
+
+/// Encode a number as SNAFU.
+///
+/// # Errors
+/// If writing the digits fails.
+///
+/// # Panics
+/// Never, `% 5` only has 5 outcomes.
+pub fn convert(sum: f64) -> Result<String> {
+    let mut s = String::new();
+
+    // Do an initial conversion to base 5
+    // If it doesn't contain anything above a 2
+    // in it this matches the snafu repr.
+    // Signed since after the units digit it goes to -1 (no trailing zeros).
+    // A sum of 0 has no log so start it at the units digit.
+    #[allow(clippy::cast_possible_truncation)]
+    let mut pow = ((sum.ln() / 5.0_f64.ln()).trunc() as i32).max(0);
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    let mut rem = sum as usize;
+    while rem != 0 {
+        #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+        let n = 5.0_f64.powi(pow) as usize;
+        let d = rem / n;
+        rem %= n;
+        pow -= 1;
+        write!(s, "{d}")?;
+    }
+    for _ in 0..=pow {
+        write!(s, "0")?;
+    }
+    if let Ok(new) = snafu(&s) {
+        #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+        if new == sum as usize {
+            return Ok(s);
+        }
+    }
+
+    // Convert to snafu by working right to left
+    s.clear();
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    let mut rem = sum as usize;
+    while rem != 0 {
+        match rem % 5 {
+            0 => write!(s, "0")?,
+            1 => write!(s, "1")?,
+            2 => write!(s, "2")?,
+            3 => write!(s, "=")?,
+            4 => write!(s, "-")?,
+            _ => panic!(),
+        }
+        rem = (rem + 2) / 5;
+    }
+    Ok(s.chars().rev().collect::<String>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, "2=-1=0");
+        Ok(())
+    }
+
+    #[test]
+    fn small_sums() -> Result<()> {
+        assert_eq!(part1(&Vec::new())?, "0");
+        for n in 1..=20 {
+            #[allow(clippy::cast_precision_loss)]
+            let s = convert(n as f64)?;
+            assert_eq!(snafu(&s)?, n, "{s}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+//! day3 benchmarks.
+aoc_common::benches!(day3, [part1, part2]);
@@ -0,0 +1,110 @@
+//! day3 advent 2022
+use aoc_common::{lines, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use std::collections::HashSet;
+
+/// Each rucksack's contents, one per line.
+pub type Input = Vec<String>;
+
+/// Parse the rucksack list.
+///
+/// # Errors
+/// If a line isn't a single field with an even number of items.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut rucks = Vec::new();
+    for (line_num, line) in lines(input) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.len() {
+            0 => return Err(ParseError::end_of_line(line_num, line, "a rucksack").into()),
+            1 => {}
+            _ => return Err(ParseError::new(line_num, line, fields[1], "end of line").into()),
+        }
+        if let Some(pos) = fields[0].find(|c: char| !c.is_ascii_alphabetic()) {
+            let item = &fields[0][pos..];
+            let item = &item[..item.chars().next().map_or(0, char::len_utf8)];
+            return Err(ParseError::new(line_num, line, item, "an item from `a` to `Z`").into());
+        }
+        if fields[0].len() % 2 != 0 {
+            return Err(
+                ParseError::new(line_num, line, fields[0], "an even number of items").into(),
+            );
+        }
+        rucks.push(String::from(fields[0]));
+    }
+    Ok(rucks)
+}
+
+/// Sum of the priorities of the item found in both compartments of each
+/// rucksack.
+///
+/// # Errors
+/// None, a rucksack with nothing in common just adds nothing.
+pub fn part1(rucks: &Input) -> Result<u32> {
+    let mut sum: u32 = 0;
+    for r in rucks {
+        let (comp1, comp2) = r.split_at(r.len() / 2);
+        let comp1 = comp1.chars().collect::<HashSet<_>>();
+        if let Some(c) = comp2.chars().find(|c| comp1.contains(c)) {
+            sum += compute(c);
+        }
+    }
+    Ok(sum)
+}
+
+/// Sum of the priorities of the badge common to each group of 3 elves.
+///
+/// # Errors
+/// [`NoAnswer`] if the elves don't split evenly into groups of 3.
+pub fn part2(rucks: &Input) -> Result<u32> {
+    if rucks.len() % 3 != 0 {
+        return Err(NoAnswer::new(format!("{} elves don't make groups of 3", rucks.len())).into());
+    }
+    let mut badges: u32 = 0;
+    for group in rucks.chunks(3) {
+        let mut common = group[0].chars().collect::<HashSet<_>>();
+        for r in &group[1..] {
+            let next = r.chars().collect::<HashSet<_>>();
+            common.retain(|c| next.contains(c));
+        }
+        for k in common {
+            badges += compute(k);
+        }
+    }
+    Ok(badges)
+}
+
+fn compute(c: char) -> u32 {
+    let mut top = 'A';
+    let mut add: u32 = 27;
+    if c >= 'a' {
+        top = 'a';
+        add = 1;
+    }
+    c as u32 - top as u32 + add
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 157);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 70);
+        Ok(())
+    }
+
+    #[test]
+    fn partial_group() -> Result<()> {
+        let rucks = parse(&EXAMPLE.lines().take(4).collect::<Vec<_>>().join("\n"))?;
+        assert!(part2(&rucks).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+}
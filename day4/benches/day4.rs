@@ -0,0 +1,2 @@
+//! day4 benchmarks.
+aoc_common::benches!(day4, [part1, part2]);
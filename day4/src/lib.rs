@@ -0,0 +1,116 @@
+//! day4 advent 2022
+use aoc_common::{lines, ParseError};
+use color_eyre::eyre::Result;
+use std::cmp::{max, min};
+
+/// One pair of elves and the (inclusive) section ranges they cover.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pair {
+    /// First elf's low section.
+    pub low1: usize,
+    /// First elf's high section.
+    pub high1: usize,
+    /// Second elf's low section.
+    pub low2: usize,
+    /// Second elf's high section.
+    pub high2: usize,
+}
+
+/// The parsed section assignments.
+pub type Input = Vec<Pair>;
+
+/// Parse the section assignment pairs.
+///
+/// # Errors
+/// If a line isn't 2 comma separated ranges of section numbers or a range
+/// ends before it starts.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut pairs = Vec::new();
+    for (line_num, l) in lines(input) {
+        let Some((p1, p2)) = l.split_once(',') else {
+            return Err(ParseError::end_of_line(line_num, l, "`,`").into());
+        };
+        let range = |r: &str| -> Result<(usize, usize)> {
+            let Some((low, high)) = r.split_once('-') else {
+                return Err(ParseError::new(line_num, l, r, "a range like `2-4`").into());
+            };
+            let low = ParseError::parse(line_num, l, low, "a section number")?;
+            let high = ParseError::parse(line_num, l, high, "a section number")?;
+            if low > high {
+                return Err(ParseError::new(line_num, l, r, "a range with low <= high").into());
+            }
+            Ok((low, high))
+        };
+        let (low1, high1) = range(p1)?;
+        let (low2, high2) = range(p2)?;
+        pairs.push(Pair {
+            low1,
+            high1,
+            low2,
+            high2,
+        });
+    }
+    Ok(pairs)
+}
+
+/// Number of pairs where one range fully contains the other.
+///
+/// # Errors
+/// None, any pair of ranges can be compared.
+pub fn part1(pairs: &Input) -> Result<usize> {
+    let mut all_overlap = 0;
+    for p in pairs {
+        let (low1, high1, low2, high2) = (p.low1, p.high1, p.low2, p.high2);
+        let (mut comp_low1, mut comp_high1, mut comp_low2, mut comp_high2) =
+            (low1, high1, low2, high2);
+        if high1 - low1 > high2 - low2 {
+            (comp_low1, comp_high1, comp_low2, comp_high2) = (low2, high2, low1, high1);
+        }
+
+        if comp_low1 >= comp_low2 && comp_high1 <= comp_high2 {
+            all_overlap += 1;
+        }
+    }
+    Ok(all_overlap)
+}
+
+/// Number of pairs whose ranges overlap at all.
+///
+/// # Errors
+/// None, as for [`part1`].
+pub fn part2(pairs: &Input) -> Result<usize> {
+    Ok(pairs
+        .iter()
+        .filter(|p| max(p.low1, p.low2) <= min(p.high1, p.high2))
+        .count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn backwards_range() -> Result<()> {
+        let err = parse("2-4,6-8\n6-96,6-4\n")
+            .unwrap_err()
+            .downcast::<ParseError>()?;
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(err.expected, "a range with low <= high");
+        assert_eq!(err.found, "`6-4`");
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+//! day5 benchmarks.
+aoc_common::benches!(day5, [part1, part2]);
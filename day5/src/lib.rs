@@ -0,0 +1,430 @@
+//! day5 advent 2022
+use aoc_common::{NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use std::fmt;
+
+/// One `move N from A to B` line. `src`/`dest` are 1 based like the input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Move {
+    /// How many crates to move.
+    pub num: usize,
+    /// Stack to take them from.
+    pub src: usize,
+    /// Stack to put them on.
+    pub dest: usize,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.num, self.src, self.dest)
+    }
+}
+
+/// Starting stacks (bottom first) and the moves to run on them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Input {
+    /// Each stack, bottom crate first.
+    pub stacks: Vec<Vec<char>>,
+    /// The rearrangement procedure.
+    pub moves: Vec<Move>,
+}
+
+/// Parse the stack drawing and the rearrangement procedure below it.
+///
+/// # Errors
+/// If the drawing isn't rows of `[X]` crates over a line numbering the
+/// stacks from 1 or a move isn't `move N from N to N`.
+pub fn parse(input: &str) -> Result<Input> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let drawing = lines
+        .iter()
+        .position(|l| l.is_empty())
+        .unwrap_or(lines.len());
+    let stacks = parse_stacks(&lines[..drawing])?;
+    let mut moves = Vec::new();
+    for (line_num, l) in lines.iter().enumerate().skip(drawing) {
+        let parts: Vec<&str> = l.split_whitespace().collect();
+        if parts.is_empty() {
+            continue;
+        }
+        if parts[0] != "move" {
+            return Err(ParseError::new(line_num, l, parts[0], "`move`").into());
+        }
+        if let Some(extra) = parts.get(6) {
+            return Err(ParseError::new(line_num, l, extra, "end of line").into());
+        }
+        let field = |i: usize, expected: &str| -> Result<usize> {
+            let Some(p) = parts.get(i) else {
+                return Err(ParseError::end_of_line(line_num, l, expected).into());
+            };
+            Ok(ParseError::parse(line_num, l, p, expected)?)
+        };
+        moves.push(Move {
+            num: field(1, "a crate count")?,
+            src: field(3, "a stack number")?,
+            dest: field(5, "a stack number")?,
+        });
+    }
+    Ok(Input { stacks, moves })
+}
+
+// The drawing is rows of crates over a footer numbering the stacks. Each
+// number sits in the same column as the letters of that stack's crates.
+fn parse_stacks(drawing: &[&str]) -> Result<Vec<Vec<char>>> {
+    let Some((&footer, rows)) = drawing.split_last() else {
+        return Err(ParseError::end_of_input(0, "", "a drawing of the stacks").into());
+    };
+    let footer_num = rows.len();
+    let mut columns = Vec::new();
+    for (i, tok) in footer.split_whitespace().enumerate() {
+        let n: usize = ParseError::parse(footer_num, footer, tok, "a stack number")?;
+        if n != i + 1 {
+            return Err(ParseError::new(
+                footer_num,
+                footer,
+                tok,
+                format!("stack number {}", i + 1),
+            )
+            .into());
+        }
+        columns.push(tok.as_ptr() as usize - footer.as_ptr() as usize);
+    }
+    if columns.is_empty() {
+        return Err(ParseError::end_of_line(footer_num, footer, "a stack number").into());
+    }
+
+    let mut stacks = vec![Vec::new(); columns.len()];
+    // Bottom up so each crate lands on the one below it.
+    for (line_num, line) in rows.iter().enumerate().rev() {
+        let height = rows.len() - 1 - line_num;
+        for (stack, &col) in stacks.iter_mut().zip(&columns) {
+            match line.as_bytes().get(col) {
+                None | Some(b' ') => continue,
+                Some(_) => {}
+            }
+            let Some(cell) = line
+                .get(col.saturating_sub(1)..(col + 2).min(line.len()))
+                .or_else(|| line.get(col..))
+            else {
+                // The stack number sits part way through a multibyte character.
+                let at = (0..col)
+                    .rev()
+                    .find(|i| line.is_char_boundary(*i))
+                    .unwrap_or(0);
+                let bad = line[at..]
+                    .chars()
+                    .next()
+                    .map_or("", |c| &line[at..at + c.len_utf8()]);
+                return Err(ParseError::new(line_num, line, bad, "a crate like `[A]`").into());
+            };
+            let mut c = cell.chars();
+            let (Some('['), Some(name), Some(']'), None) = (c.next(), c.next(), c.next(), c.next())
+            else {
+                return Err(ParseError::new(line_num, line, cell, "a crate like `[A]`").into());
+            };
+            if stack.len() != height {
+                return Err(ParseError::new(
+                    line_num,
+                    line,
+                    cell,
+                    "a gap since there's no crate underneath",
+                )
+                .into());
+            }
+            stack.push(name);
+        }
+    }
+    Ok(stacks)
+}
+
+/// A crane model. Each one agrees on which crates a move takes but not on
+/// what order they come down in.
+pub trait Crane {
+    /// Carry out `m`, the move at index `i` (for error messages).
+    ///
+    /// # Errors
+    /// [`NoAnswer`] if the move names a missing stack or takes from an
+    /// empty one.
+    fn apply(&self, stacks: &mut [Vec<char>], i: usize, m: &Move) -> Result<()>;
+}
+
+/// Moves one crate at a time so a group comes down reversed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn apply(&self, stacks: &mut [Vec<char>], i: usize, m: &Move) -> Result<()> {
+        for _ in 0..m.num {
+            let v = take(stacks, i, m)?;
+            stack(stacks, i, m.dest)?.push(v);
+        }
+        Ok(())
+    }
+}
+
+/// Moves the whole group at once so it keeps its order.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn apply(&self, stacks: &mut [Vec<char>], i: usize, m: &Move) -> Result<()> {
+        grab(stacks, i, m, m.num)
+    }
+}
+
+/// Moves up to `capacity` crates per grab, each grab keeping its order.
+/// A capacity of 1 is the 9000 and anything at least as big as the largest
+/// move is the 9001.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Bounded {
+    /// Most crates lifted at once.
+    pub capacity: usize,
+}
+
+impl Crane for Bounded {
+    fn apply(&self, stacks: &mut [Vec<char>], i: usize, m: &Move) -> Result<()> {
+        if self.capacity == 0 && m.num > 0 {
+            return Err(NoAnswer::new(format!(
+                "move {} needs a crane that can lift something",
+                i + 1
+            ))
+            .into());
+        }
+        let mut left = m.num;
+        while left > 0 {
+            let n = left.min(self.capacity);
+            grab(stacks, i, m, n)?;
+            left -= n;
+        }
+        Ok(())
+    }
+}
+
+/// Top crates after moving one crate at a time (the 9000 crane).
+///
+/// # Errors
+/// [`NoAnswer`] if a move names a missing stack, takes from an empty stack
+/// or a stack ends up empty.
+pub fn part1(input: &Input) -> Result<String> {
+    rearrange(input, &CrateMover9000, |_, _| {})
+}
+
+/// Top crates after moving whole groups at once (the 9001 crane).
+///
+/// # Errors
+/// [`NoAnswer`] if a move names a missing stack, takes from an empty stack
+/// or a stack ends up empty.
+pub fn part2(input: &Input) -> Result<String> {
+    rearrange(input, &CrateMover9001, |_, _| {})
+}
+
+/// Top crates after running every move with `crane`. `after` sees each move
+/// and the stacks once it's done.
+///
+/// # Errors
+/// [`NoAnswer`] if a move names a missing stack, takes from an empty stack
+/// or a stack ends up empty.
+pub fn rearrange(
+    input: &Input,
+    crane: &impl Crane,
+    mut after: impl FnMut(&Move, &[Vec<char>]),
+) -> Result<String> {
+    let mut stacks = input.stacks.clone();
+    for (i, m) in input.moves.iter().enumerate() {
+        crane.apply(&mut stacks, i, m)?;
+        after(m, &stacks);
+    }
+    tops(&stacks)
+}
+
+/// Draw `stacks` the way the input does, footer included.
+#[must_use]
+pub fn render(stacks: &[Vec<char>]) -> String {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut out = String::new();
+    for row in (0..height).rev() {
+        let cells = stacks
+            .iter()
+            .map(|s| s.get(row).map_or(String::from("   "), |c| format!("[{c}]")))
+            .collect::<Vec<_>>();
+        out.push_str(&cells.join(" "));
+        out.push('\n');
+    }
+    let footer = (1..=stacks.len())
+        .map(|n| format!("{n:^3}"))
+        .collect::<Vec<_>>();
+    out.push_str(&footer.join(" "));
+    out.push('\n');
+    out
+}
+
+// Lift the top `n` crates off the source of move `i` in one go.
+fn grab(stacks: &mut [Vec<char>], i: usize, m: &Move, n: usize) -> Result<()> {
+    let mut t = Vec::<char>::new();
+    for _ in 0..n {
+        t.push(take(stacks, i, m)?);
+    }
+    t.reverse();
+    stack(stacks, i, m.dest)?.extend(t);
+    Ok(())
+}
+
+// Stack `n` (1 based) for move `i`.
+fn stack(stacks: &mut [Vec<char>], i: usize, n: usize) -> Result<&mut Vec<char>> {
+    let len = stacks.len();
+    match n.checked_sub(1).and_then(|n| stacks.get_mut(n)) {
+        Some(s) => Ok(s),
+        None => Err(NoAnswer::new(format!(
+            "move {} uses stack {n} but there are only {len}",
+            i + 1
+        ))
+        .into()),
+    }
+}
+
+// Take the top crate off the source stack of move `i`.
+fn take(stacks: &mut [Vec<char>], i: usize, m: &Move) -> Result<char> {
+    match stack(stacks, i, m.src)?.pop() {
+        Some(c) => Ok(c),
+        None => Err(NoAnswer::new(format!(
+            "move {} takes from stack {} once it's empty",
+            i + 1,
+            m.src
+        ))
+        .into()),
+    }
+}
+
+fn tops(stacks: &[Vec<char>]) -> Result<String> {
+    let mut s = String::new();
+    for (i, v) in stacks.iter().enumerate() {
+        let Some(c) = v.last() else {
+            return Err(NoAnswer::new(format!("stack {} ends up empty", i + 1)).into());
+        };
+        s.push(*c);
+    }
+    Ok(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, "CMZ");
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, "MCD");
+        Ok(())
+    }
+
+    #[test]
+    fn drawing() -> Result<()> {
+        let input = parse(EXAMPLE)?;
+        assert_eq!(
+            input.stacks,
+            vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]
+        );
+        assert_eq!(input.moves.len(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_drawing() -> Result<()> {
+        let floating = parse("[A]    \n    [B]\n 1   2 \n")
+            .unwrap_err()
+            .downcast::<ParseError>()?;
+        assert_eq!((floating.line, floating.column), (1, 1));
+        let footer = parse("[A] [B]\n 1   3 \n")
+            .unwrap_err()
+            .downcast::<ParseError>()?;
+        assert_eq!(
+            (footer.line, footer.expected.as_str()),
+            (2, "stack number 2")
+        );
+        let crate_ = parse("[A] (B)\n 1   2 \n")
+            .unwrap_err()
+            .downcast::<ParseError>()?;
+        assert_eq!((crate_.line, crate_.column), (1, 5));
+        Ok(())
+    }
+
+    #[test]
+    fn multibyte_drawing() -> Result<()> {
+        let err = parse("    éé\n 1   2 \n")
+            .unwrap_err()
+            .downcast::<ParseError>()?;
+        assert_eq!((err.line, err.column), (1, 5));
+        assert_eq!(err.found, "`é`");
+        Ok(())
+    }
+
+    #[test]
+    fn empty_stack() {
+        let input = Input {
+            stacks: vec![vec!['A'], vec!['B']],
+            moves: vec![Move {
+                num: 2,
+                src: 1,
+                dest: 2,
+            }],
+        };
+        assert!(part1(&input).unwrap_err().is::<NoAnswer>());
+        assert!(part2(&input).unwrap_err().is::<NoAnswer>());
+    }
+
+    #[test]
+    fn missing_stack() {
+        let input = Input {
+            stacks: vec![vec!['A'], vec!['B']],
+            moves: vec![Move {
+                num: 1,
+                src: 1,
+                dest: 3,
+            }],
+        };
+        assert!(part1(&input).unwrap_err().is::<NoAnswer>());
+    }
+
+    #[test]
+    fn cranes() -> Result<()> {
+        let m = Move {
+            num: 3,
+            src: 1,
+            dest: 2,
+        };
+        let lift = |crane: &dyn Crane| -> Result<Vec<char>> {
+            let mut stacks = vec![vec!['A', 'B', 'C'], vec![]];
+            crane.apply(&mut stacks, 0, &m)?;
+            Ok(stacks.swap_remove(1))
+        };
+        assert_eq!(lift(&CrateMover9000)?, vec!['C', 'B', 'A']);
+        assert_eq!(lift(&CrateMover9001)?, vec!['A', 'B', 'C']);
+        assert_eq!(lift(&Bounded { capacity: 2 })?, vec!['B', 'C', 'A']);
+        assert!(lift(&Bounded { capacity: 0 }).unwrap_err().is::<NoAnswer>());
+
+        let example = parse(EXAMPLE)?;
+        let noop = |_: &Move, _: &[Vec<char>]| {};
+        assert_eq!(rearrange(&example, &Bounded { capacity: 1 }, noop)?, "CMZ");
+        assert_eq!(rearrange(&example, &Bounded { capacity: 3 }, noop)?, "MCD");
+        Ok(())
+    }
+
+    #[test]
+    fn render_round_trips() -> Result<()> {
+        let input = parse(EXAMPLE)?;
+        let drawing = render(&input.stacks);
+        assert_eq!(
+            drawing,
+            "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n"
+        );
+        assert_eq!(parse(&drawing)?.stacks, input.stacks);
+        Ok(())
+    }
+}
@@ -1,88 +1,64 @@
 //! day5 advent 2022
+use aoc_common::Report;
 use clap::Parser;
 use color_eyre::eyre::Result;
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
-use std::path::Path;
+use day5::{
+    parse, part1, part2, rearrange, render, Bounded, Crane, CrateMover9000, CrateMover9001, Input,
+};
 
 #[derive(Parser)]
 #[command(author, version, about)]
 struct Args {
-    #[arg(long, default_value_t = String::from("input.txt"))]
-    filename: String,
+    #[command(flatten)]
+    common: aoc_common::Args,
 
+    /// Draw the stacks after every move.
     #[arg(long, default_value_t = false)]
-    debug: bool,
+    trace: bool,
+
+    /// Run part 2 with a crane that lifts at most this many crates at once
+    /// instead of the 9001.
+    #[arg(long)]
+    capacity: Option<usize>,
+}
+
+// Replay the moves drawing the stacks after each one. Done before the part
+// is timed so the drawing doesn't count against it.
+fn trace(report: &Report, input: &Input, crane: &impl Crane) -> Result<()> {
+    report.show(render(&input.stacks));
+    rearrange(input, crane, |m, stacks| {
+        report.show(format_args!("{m}\n{}", render(stacks)));
+    })?;
+    Ok(())
 }
 
 fn main() -> Result<()> {
     color_eyre::install()?;
     let args: Args = Args::parse();
+    let report = args.common.report(5);
 
-    let filename = Path::new(env!("CARGO_MANIFEST_DIR")).join(args.filename);
-    let file = File::open(filename)?;
-    let lines: Vec<String> = io::BufReader::new(file).lines().flatten().collect();
-
-    let mut stacks = vec![
-        vec!['D', 'T', 'W', 'F', 'J', 'S', 'H', 'N'],
-        vec!['H', 'R', 'P', 'Q', 'T', 'N', 'B', 'G'],
-        vec!['L', 'Q', 'V'],
-        vec!['N', 'B', 'S', 'W', 'R', 'Q'],
-        vec!['N', 'D', 'F', 'T', 'V', 'M', 'B'],
-        vec!['M', 'D', 'B', 'V', 'H', 'T', 'R'],
-        vec!['D', 'B', 'Q', 'J'],
-        vec!['D', 'N', 'J', 'V', 'R', 'Z', 'H', 'Q'],
-        vec!['B', 'N', 'H', 'M', 'S'],
-    ];
-    let mut stacks9001 = vec![
-        vec!['D', 'T', 'W', 'F', 'J', 'S', 'H', 'N'],
-        vec!['H', 'R', 'P', 'Q', 'T', 'N', 'B', 'G'],
-        vec!['L', 'Q', 'V'],
-        vec!['N', 'B', 'S', 'W', 'R', 'Q'],
-        vec!['N', 'D', 'F', 'T', 'V', 'M', 'B'],
-        vec!['M', 'D', 'B', 'V', 'H', 'T', 'R'],
-        vec!['D', 'B', 'Q', 'J'],
-        vec!['D', 'N', 'J', 'V', 'R', 'Z', 'H', 'Q'],
-        vec!['B', 'N', 'H', 'M', 'S'],
-    ];
-    for (line_num, l) in lines.iter().enumerate() {
-        let parts: Vec<&str> = l.split_whitespace().collect();
-        if parts.is_empty() || parts[0] != "move" {
-            if args.debug {
-                println!("skipping - {l}");
-            }
-            continue;
-        }
-        assert!(parts.len() == 6, "{} - bad line - {l}", line_num + 1);
-        let num = parts[1].parse::<u32>()?;
-        let src = parts[3].parse::<usize>()?;
-        let dest = parts[5].parse::<usize>()?;
-
-        let mut t = Vec::<char>::new();
-        for i in 0..num {
-            let v = stacks[src - 1].pop().unwrap();
-            if args.debug {
-                println!("{} - {l} - {i} {src} {dest}", line_num + 1);
-            }
-            stacks[dest - 1].push(v);
-            let v = stacks9001[src - 1].pop().unwrap();
-            t.push(v);
-        }
-        t.reverse();
-        for v in t {
-            stacks9001[dest - 1].push(v);
+    let file = args.common.input(env!("CARGO_MANIFEST_DIR"))?;
+    let input = file.parse(parse)?;
+    if args.common.debug {
+        report.show(format_args!("{:?}", input.stacks));
+        for m in &input.moves {
+            report.show(format_args!("{m:?}"));
         }
     }
-    print!("part1 - ");
-    for mut v in stacks {
-        print!("{}", v.pop().unwrap());
+    if args.trace && report.wants(1) {
+        trace(&report, &input, &CrateMover9000)?;
     }
-    println!();
-    print!("part2 - ");
-    for mut v in stacks9001 {
-        print!("{}", v.pop().unwrap());
+    report.part(1, "part1 - ", || part1(&input))?;
+    let bounded = args.capacity.map(|capacity| Bounded { capacity });
+    if args.trace && report.wants(2) {
+        match &bounded {
+            Some(crane) => trace(&report, &input, crane)?,
+            None => trace(&report, &input, &CrateMover9001)?,
+        }
     }
-    println!();
+    report.part(2, "part2 - ", || match &bounded {
+        Some(crane) => rearrange(&input, crane, |_, _| {}),
+        None => part2(&input),
+    })?;
     Ok(())
 }
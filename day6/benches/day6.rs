@@ -0,0 +1,2 @@
+//! day6 benchmarks.
+aoc_common::benches!(day6, [part1, part2]);
@@ -0,0 +1,74 @@
+//! day6 advent 2022
+use aoc_common::{NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use std::collections::HashSet;
+
+/// The datastream buffer.
+pub type Input = String;
+
+const PART1: usize = 4;
+const PART2: usize = 14;
+
+/// Parse the datastream. Only the first line is used.
+///
+/// # Errors
+/// If the datastream is shorter than a packet marker.
+pub fn parse(input: &str) -> Result<Input> {
+    let l = input.lines().next().unwrap_or_default();
+    if l.len() < PART1 {
+        return Err(ParseError::end_of_line(0, l, format!("at least {PART1} characters")).into());
+    }
+    Ok(String::from(l))
+}
+
+/// Characters processed before the first start-of-packet marker.
+///
+/// # Errors
+/// [`NoAnswer`] if there isn't one.
+pub fn part1(stream: &Input) -> Result<usize> {
+    decode(stream, PART1)
+}
+
+/// Characters processed before the first start-of-message marker.
+///
+/// # Errors
+/// [`NoAnswer`] if there isn't one.
+pub fn part2(stream: &Input) -> Result<usize> {
+    decode(stream, PART2)
+}
+
+// Position just past the first `size` characters that are all different.
+fn decode(l: &str, size: usize) -> Result<usize> {
+    l.as_bytes()
+        .windows(size)
+        .position(|w| w.iter().collect::<HashSet<_>>().len() == size)
+        .map(|p| p + size)
+        .ok_or_else(|| NoAnswer::new(format!("no {size} different characters in a row")).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 7);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 19);
+        Ok(())
+    }
+
+    #[test]
+    fn no_marker() -> Result<()> {
+        let stream = parse("abcabcabcabcabcabc")?;
+        assert!(part1(&stream).unwrap_err().is::<NoAnswer>());
+        assert_eq!(part1(&parse("abcd")?)?, 4);
+        Ok(())
+    }
+}
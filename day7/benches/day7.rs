@@ -0,0 +1,2 @@
+//! day7 benchmarks.
+aoc_common::benches!(day7, [part1, part2]);
@@ -0,0 +1,218 @@
+//! day7 advent 2022
+use aoc_common::{lines, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use slab_tree::tree::TreeBuilder;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+struct Ent {
+    name: String,
+    size: usize,
+}
+
+const TOTAL_SIZE: usize = 70_000_000;
+const REQUIRED: usize = 30_000_000;
+
+/// The filesystem reconstructed from the terminal output.
+#[derive(Debug)]
+pub struct Input {
+    /// Total size of every directory (including `/`).
+    pub dirs: Vec<usize>,
+    /// Total size used on the filesystem.
+    pub used: usize,
+    /// The tree with directory sizes filled in, for debugging.
+    pub listing: String,
+}
+
+/// Replay the terminal output into directory sizes.
+///
+/// # Errors
+/// On malformed lines or a `cd` into an unknown directory.
+///
+/// # Panics
+/// If the tree loses its root.
+#[allow(clippy::too_many_lines)]
+pub fn parse(input: &str) -> Result<Input> {
+    let mut tree = TreeBuilder::new()
+        .with_root(Ent {
+            name: String::from("/"),
+            size: 0,
+        })
+        .build();
+
+    let root_id = tree.root_id().unwrap();
+    let mut cur_id = root_id;
+
+    for (line_num, line) in lines(input) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+
+        let field = |i: usize, expected: &str| -> Result<&str> {
+            match fields.get(i) {
+                Some(f) => Ok(f),
+                None => Err(ParseError::end_of_line(line_num, line, expected).into()),
+            }
+        };
+        let extra = |i: usize| -> Result<()> {
+            match fields.get(i) {
+                Some(f) => Err(ParseError::new(line_num, line, f, "end of line").into()),
+                None => Ok(()),
+            }
+        };
+
+        if field(0, "a command or a listing")? == "$" {
+            match field(1, "`cd` or `ls`")? {
+                "cd" => {
+                    let dir = field(2, "a directory")?;
+                    extra(3)?;
+                    match dir {
+                        "/" => {
+                            cur_id = root_id;
+                        }
+                        ".." => {
+                            let Some(parent) = tree.get(cur_id).unwrap().parent() else {
+                                return Err(ParseError::new(
+                                    line_num,
+                                    line,
+                                    dir,
+                                    "a directory below `/`",
+                                )
+                                .into());
+                            };
+                            cur_id = parent.node_id();
+                        }
+                        _ => {
+                            let node = tree.get(cur_id).unwrap();
+                            let mut found = false;
+                            for n in node.children() {
+                                if n.data().name == fields[2] {
+                                    cur_id = n.node_id();
+                                    found = true;
+                                    break;
+                                }
+                            }
+                            if !found {
+                                return Err(ParseError::new(
+                                    line_num,
+                                    line,
+                                    dir,
+                                    "a directory already listed",
+                                )
+                                .into());
+                            }
+                        }
+                    }
+                }
+                "ls" => {
+                    // nothing happens here
+                    extra(2)?;
+                }
+                f => return Err(ParseError::new(line_num, line, f, "`cd` or `ls`").into()),
+            }
+        } else {
+            let name = field(1, "a name")?;
+            extra(2)?;
+            let mut size = 0;
+            if fields[0] != "dir" {
+                size = ParseError::parse(line_num, line, fields[0], "`dir` or a file size")?;
+            }
+            let mut node = tree.get_mut(cur_id).unwrap();
+            node.append(Ent {
+                name: String::from(name),
+                size,
+            });
+        }
+    }
+
+    let mut hm = HashMap::new();
+    for node in tree.root().unwrap().traverse_post_order() {
+        if let Some(p) = node.parent() {
+            let mut size = node.data().size;
+            // Directories have no size initially but the hash
+            // map entry for it will have the size we need to insert.
+            // Then we add the size to the parent node. An empty directory
+            // or a 0 byte file never got an entry.
+            if size == 0 {
+                size = hm.get(&node.node_id()).copied().unwrap_or(0);
+            }
+            hm.entry(p.node_id())
+                .and_modify(|s| *s += size)
+                .or_insert(size);
+        }
+    }
+
+    let mut dirs = Vec::new();
+    for (k, size) in &hm {
+        tree.get_mut(*k).unwrap().data().size = *size;
+        dirs.push(*size);
+    }
+    let mut listing = String::new();
+    tree.write_formatted(&mut listing)?;
+    Ok(Input {
+        dirs,
+        // Nothing listed at all leaves nothing in the map.
+        used: hm.get(&root_id).copied().unwrap_or_default(),
+        listing,
+    })
+}
+
+/// Sum of all directories of at most 100000.
+///
+/// # Errors
+/// None, no directories just sum to 0.
+pub fn part1(fs: &Input) -> Result<usize> {
+    Ok(fs.dirs.iter().filter(|s| **s <= 100_000).sum())
+}
+
+/// Size of the smallest directory that frees up enough space for the update.
+///
+/// # Errors
+/// [`NoAnswer`] if there's already enough space, the disk is overfull or no
+/// directory is large enough.
+pub fn part2(fs: &Input) -> Result<usize> {
+    let Some(free) = TOTAL_SIZE.checked_sub(fs.used) else {
+        return Err(NoAnswer::new(format!("{} used on a {TOTAL_SIZE} disk", fs.used)).into());
+    };
+    let Some(needed) = REQUIRED.checked_sub(free).filter(|n| *n > 0) else {
+        return Err(NoAnswer::new(format!("{free} is already free")).into());
+    };
+    match fs.dirs.iter().filter(|s| **s >= needed).min() {
+        Some(s) => Ok(*s),
+        None => Err(NoAnswer::new(format!("no directory frees up {needed}")).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 95437);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 24_933_642);
+        Ok(())
+    }
+
+    #[test]
+    fn enough_space() -> Result<()> {
+        let fs = parse("$ cd /\n$ ls\n100 a.txt\n")?;
+        assert_eq!(part1(&fs)?, 100);
+        assert!(part2(&fs).unwrap_err().is::<NoAnswer>());
+        assert!(part2(&parse("")?).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+
+    #[test]
+    fn empty_directory() -> Result<()> {
+        let fs = parse("$ cd /\n$ ls\ndir a\n0 b.txt\n")?;
+        assert_eq!(fs.used, 0);
+        assert_eq!(part1(&fs)?, 0);
+        Ok(())
+    }
+}
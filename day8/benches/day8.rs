@@ -0,0 +1,2 @@
+//! day8 benchmarks.
+aoc_common::benches!(day8, [part1, part2]);
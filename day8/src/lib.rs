@@ -0,0 +1,211 @@
+//! day8 advent 2022
+use aoc_common::{lines, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+
+/// Tree heights indexed by `[y][x]`.
+pub type Input = Vec<Vec<usize>>;
+
+/// Parse the grid of tree heights.
+///
+/// # Errors
+/// If a height isn't a digit or the rows aren't all the same length.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut map = Vec::new();
+    for (line_num, line) in lines(input) {
+        let mut row = Vec::new();
+        for (pos, c) in line.char_indices() {
+            let c = &line[pos..pos + c.len_utf8()];
+            row.push(ParseError::parse(line_num, line, c, "a tree height")?);
+        }
+        if let Some(first) = map.first().map(Vec::len).filter(|l| *l != row.len()) {
+            return Err(if row.len() > first {
+                ParseError::new(
+                    line_num,
+                    line,
+                    line.get(first..).unwrap_or(line),
+                    "end of line",
+                )
+            } else {
+                ParseError::end_of_line(line_num, line, format!("{first} trees"))
+            }
+            .into());
+        }
+        map.push(row);
+    }
+    Ok(map)
+}
+
+/// Which trees are visible from outside the grid, indexed by `[y][x]`.
+///
+/// # Errors
+/// [`NoAnswer`] if the grid is empty.
+pub fn visible(map: &Input) -> Result<Vec<Vec<bool>>> {
+    let (max_x, max_y) = bounds(map)?;
+    let mut seen = Vec::new();
+    for y in 0..=max_y {
+        let mut row = Vec::new();
+        let mut val = false;
+        if y == 0 || y == max_y {
+            val = true;
+        }
+        for x in 0..=max_x {
+            if x == 0 || x == max_x {
+                row.push(true);
+            } else {
+                row.push(val);
+            }
+        }
+        seen.push(row);
+    }
+    for y in 0..=max_y {
+        // edge always visible
+        let mut tallest = map[y][0];
+        for x in 0..=max_x {
+            if map[y][x] > tallest && x != max_x && y != 0 && y != max_y && x != 0 {
+                seen[y][x] = true;
+                tallest = map[y][x];
+            }
+        }
+
+        if y == 0 {
+            for x in 0..=max_x {
+                let mut tallest = map[0][x];
+                for yy in 0..=max_y {
+                    if map[yy][x] > tallest && yy != max_y && yy != 0 && x != 0 && x != max_x {
+                        seen[yy][x] = true;
+                        tallest = map[yy][x];
+                    }
+                }
+            }
+        }
+        if y == max_y {
+            for x in 0..=max_x {
+                let mut tallest = map[max_y][x];
+                for yy in (0..=max_y).rev() {
+                    if map[yy][x] > tallest && yy != 0 && yy != max_y && x != 0 && x != max_x {
+                        seen[yy][x] = true;
+                        tallest = map[yy][x];
+                    }
+                }
+            }
+        }
+
+        tallest = map[y][max_x];
+        for x in (0..=max_x).rev() {
+            if map[y][x] > tallest && x != 0 && y != 0 && y != max_y && x != max_x {
+                seen[y][x] = true;
+                tallest = map[y][x];
+            }
+        }
+    }
+    Ok(seen)
+}
+
+// Largest x and y in the grid.
+fn bounds(map: &Input) -> Result<(usize, usize)> {
+    match map.first().map(Vec::len) {
+        Some(w) if w > 0 => Ok((w - 1, map.len() - 1)),
+        _ => Err(NoAnswer::new("there are no trees").into()),
+    }
+}
+
+/// Number of trees visible from outside the grid.
+///
+/// # Errors
+/// [`NoAnswer`] if the grid is empty.
+pub fn part1(map: &Input) -> Result<usize> {
+    Ok(visible(map)?.iter().flatten().filter(|v| **v).count())
+}
+
+/// Highest scenic score of any tree.
+///
+/// # Errors
+/// [`NoAnswer`] if the grid is empty.
+pub fn part2(map: &Input) -> Result<usize> {
+    let (max_x, max_y) = bounds(map)?;
+    let mut scenic = 0;
+    for y in 1..max_y {
+        for x in 1..max_x {
+            let here = map[y][x];
+            let mut sum = 1;
+            // look up
+            let mut dist = 0;
+            for yy in (0..y).rev() {
+                dist += 1;
+                if map[yy][x] >= here {
+                    break;
+                }
+            }
+            sum *= dist;
+
+            dist = 0;
+            // look down
+            for m in map.iter().take(max_y + 1).skip(y + 1) {
+                dist += 1;
+                if m[x] >= here {
+                    break;
+                }
+            }
+            sum *= dist;
+
+            dist = 0;
+            // look left
+            for xx in (0..x).rev() {
+                dist += 1;
+                if map[y][xx] >= here {
+                    break;
+                }
+            }
+            sum *= dist;
+
+            dist = 0;
+            // look right
+            for m in map[y].iter().take(max_x + 1).skip(x + 1) {
+                dist += 1;
+                if *m >= here {
+                    break;
+                }
+            }
+            sum *= dist;
+
+            if sum > scenic {
+                scenic = sum;
+            }
+        }
+    }
+    Ok(scenic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 21);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 8);
+        Ok(())
+    }
+
+    #[test]
+    fn empty() -> Result<()> {
+        let map = parse("")?;
+        assert!(part1(&map).unwrap_err().is::<NoAnswer>());
+        assert!(part2(&map).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+
+    #[test]
+    fn not_square() -> Result<()> {
+        // Everything but the 1 is visible, including the whole bottom edge.
+        assert_eq!(part1(&parse("999\n919\n999\n909\n")?)?, 11);
+        Ok(())
+    }
+}
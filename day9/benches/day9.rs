@@ -0,0 +1,2 @@
+//! day9 benchmarks.
+aoc_common::benches!(day9, [part1, part2]);
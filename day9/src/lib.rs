@@ -0,0 +1,329 @@
+//! day9 advent 2022
+use aoc_common::{lines, NoAnswer, ParseError};
+use color_eyre::eyre::Result;
+use std::collections::HashSet;
+use strum_macros::{Display, EnumString};
+
+/// A direction the head (or a following knot) moves.
+#[derive(Clone, Debug, Display, EnumString)]
+pub enum Moves {
+    /// Right.
+    R,
+    /// Up.
+    U,
+    /// Left.
+    L,
+    /// Down.
+    D,
+    /// Up and right.
+    NE,
+    /// Down and right.
+    SE,
+    /// Up and left.
+    NW,
+    /// Down and left.
+    SW,
+}
+
+#[derive(Clone, Debug, Display, EnumString, PartialEq)]
+enum Adjacent {
+    On,
+    W,
+    E,
+    S,
+    N,
+    SE,
+    SW,
+    NE,
+    NW,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Location(i32, i32);
+
+const PART1: usize = 1;
+const PART2: usize = 9;
+
+/// One line of the input. The head moves `count` steps in `dir`.
+#[derive(Clone, Debug)]
+pub struct Motion {
+    /// Direction to move. Only `R`, `U`, `L` and `D` come from the input.
+    pub dir: Moves,
+    /// Number of steps.
+    pub count: usize,
+}
+
+/// The series of head motions.
+pub type Input = Vec<Motion>;
+
+/// Parse the head motions.
+///
+/// # Errors
+/// If a line isn't a direction and a step count.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut motions = Vec::new();
+    for (line_num, line) in lines(input) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if let Some(extra) = parts.get(2) {
+            return Err(ParseError::new(line_num, line, extra, "end of line").into());
+        }
+        let dir = match parts.first().copied().unwrap_or_default() {
+            "R" => Moves::R,
+            "U" => Moves::U,
+            "L" => Moves::L,
+            "D" => Moves::D,
+            p => {
+                return Err(
+                    ParseError::new(line_num, line, p, "one of `R`, `U`, `L` or `D`").into(),
+                );
+            }
+        };
+        let count = ParseError::parse(
+            line_num,
+            line,
+            parts.get(1).copied().unwrap_or_default(),
+            "a step count",
+        )?;
+        motions.push(Motion { dir, count });
+    }
+    Ok(motions)
+}
+
+/// Number of positions the tail visits with a 2 knot rope.
+///
+/// # Errors
+/// None, the tail always visits at least where it starts.
+pub fn part1(motions: &Input) -> Result<usize> {
+    visited(motions, PART1)
+}
+
+/// Number of positions the tail visits with a 10 knot rope.
+///
+/// # Errors
+/// None, as for [`part1`].
+pub fn part2(motions: &Input) -> Result<usize> {
+    visited(motions, PART2)
+}
+
+/// Number of positions the tail visits for a rope with `size` knots
+/// following the head.
+///
+/// # Errors
+/// [`NoAnswer`] if `size` is 0 since then there's no tail.
+pub fn visited(motions: &[Motion], size: usize) -> Result<usize> {
+    let Some(tail) = size.checked_sub(1) else {
+        return Err(NoAnswer::new("a rope needs at least one knot").into());
+    };
+    let mut hm = HashSet::new();
+    let mut cur = Vec::new();
+    let mut adj = Vec::new();
+    // Add an extra so we can overflow setup in recursion later w/o panic.
+    // slight memory usage.
+    for _ in 0..=size {
+        adj.push(Adjacent::On);
+        cur.push(Location(0, 0));
+    }
+    hm.insert(cur[tail].clone());
+
+    for m in motions {
+        for _ in 0..m.count {
+            process(0, &mut adj, &m.dir, &mut cur, tail);
+            hm.insert(cur[tail].clone());
+        }
+    }
+    Ok(hm.len())
+}
+
+#[allow(clippy::too_many_lines)]
+fn process(i: usize, adj: &mut Vec<Adjacent>, dir: &Moves, cur: &mut Vec<Location>, tail: usize) {
+    if i > tail {
+        return;
+    }
+    match (&adj[i], dir) {
+        (Adjacent::On, Moves::L)
+        | (Adjacent::S, Moves::SW)
+        | (Adjacent::N, Moves::NW)
+        | (Adjacent::SE, Moves::D)
+        | (Adjacent::NE, Moves::U) => {
+            adj[i] = Adjacent::E;
+        }
+        (Adjacent::On, Moves::U)
+        | (Adjacent::SW, Moves::L)
+        | (Adjacent::SE, Moves::R)
+        | (Adjacent::E, Moves::NE)
+        | (Adjacent::W, Moves::NW) => {
+            adj[i] = Adjacent::S;
+        }
+        (Adjacent::On, Moves::D)
+        | (Adjacent::NW, Moves::L)
+        | (Adjacent::NE, Moves::R)
+        | (Adjacent::E, Moves::SE)
+        | (Adjacent::W, Moves::SW) => {
+            adj[i] = Adjacent::N;
+        }
+        (Adjacent::On, Moves::NE) | (Adjacent::S, Moves::R) | (Adjacent::W, Moves::U) => {
+            adj[i] = Adjacent::SW;
+        }
+        (Adjacent::On, Moves::SE) | (Adjacent::N, Moves::R) | (Adjacent::W, Moves::D) => {
+            adj[i] = Adjacent::NW;
+        }
+        (Adjacent::On, Moves::NW) | (Adjacent::S, Moves::L) | (Adjacent::E, Moves::U) => {
+            adj[i] = Adjacent::SE;
+        }
+        (Adjacent::On, Moves::SW) | (Adjacent::N, Moves::L) | (Adjacent::E, Moves::D) => {
+            adj[i] = Adjacent::NE;
+        }
+
+        (Adjacent::W, Moves::R) => {
+            adj[i] = Adjacent::W;
+            cur[i] = Location(cur[i].0 + 1, cur[i].1);
+            process(i + 1, adj, dir, cur, tail);
+        }
+        (Adjacent::W, Moves::L)
+        | (Adjacent::NW, Moves::NW)
+        | (Adjacent::NE, Moves::NE)
+        | (Adjacent::SW, Moves::SW)
+        | (Adjacent::SE, Moves::SE)
+        | (Adjacent::N, Moves::U)
+        | (Adjacent::S, Moves::D)
+        | (Adjacent::E, Moves::R) => {
+            adj[i] = Adjacent::On;
+        }
+        (Adjacent::W, Moves::NE) | (Adjacent::SW, Moves::R) => {
+            adj[i] = Adjacent::W;
+            cur[i] = Location(cur[i].0 + 1, cur[i].1 + 1);
+            process(i + 1, adj, &Moves::NE, cur, tail);
+        }
+        (Adjacent::W, Moves::SE) | (Adjacent::NW, Moves::R) => {
+            adj[i] = Adjacent::W;
+            cur[i] = Location(cur[i].0 + 1, cur[i].1 - 1);
+            process(i + 1, adj, &Moves::SE, cur, tail);
+        }
+
+        (Adjacent::E, Moves::L) => {
+            adj[i] = Adjacent::E;
+            cur[i] = Location(cur[i].0 - 1, cur[i].1);
+            process(i + 1, adj, dir, cur, tail);
+        }
+        (Adjacent::E, Moves::NW) | (Adjacent::SE, Moves::L) => {
+            adj[i] = Adjacent::E;
+            cur[i] = Location(cur[i].0 - 1, cur[i].1 + 1);
+            process(i + 1, adj, &Moves::NW, cur, tail);
+        }
+        (Adjacent::E, Moves::SW) | (Adjacent::NE, Moves::L) => {
+            adj[i] = Adjacent::E;
+            cur[i] = Location(cur[i].0 - 1, cur[i].1 - 1);
+            process(i + 1, adj, &Moves::SW, cur, tail);
+        }
+
+        (Adjacent::S, Moves::U) => {
+            adj[i] = Adjacent::S;
+            cur[i] = Location(cur[i].0, cur[i].1 + 1);
+            process(i + 1, adj, dir, cur, tail);
+        }
+        (Adjacent::S, Moves::NE) | (Adjacent::SW, Moves::U) => {
+            adj[i] = Adjacent::S;
+            cur[i] = Location(cur[i].0 + 1, cur[i].1 + 1);
+            process(i + 1, adj, &Moves::NE, cur, tail);
+        }
+        (Adjacent::S, Moves::SE)
+        | (Adjacent::NW, Moves::U)
+        | (Adjacent::SW, Moves::D)
+        | (Adjacent::N, Moves::NE)
+        | (Adjacent::On, Moves::R) => {
+            adj[i] = Adjacent::W;
+        }
+        (Adjacent::S, Moves::NW) | (Adjacent::SE, Moves::U) => {
+            adj[i] = Adjacent::S;
+            cur[i] = Location(cur[i].0 - 1, cur[i].1 + 1);
+            process(i + 1, adj, &Moves::NW, cur, tail);
+        }
+
+        (Adjacent::N, Moves::D) => {
+            adj[i] = Adjacent::N;
+            cur[i] = Location(cur[i].0, cur[i].1 - 1);
+            process(i + 1, adj, dir, cur, tail);
+        }
+        (Adjacent::N, Moves::SE) | (Adjacent::NW, Moves::D) => {
+            adj[i] = Adjacent::N;
+            cur[i] = Location(cur[i].0 + 1, cur[i].1 - 1);
+            process(i + 1, adj, &Moves::SE, cur, tail);
+        }
+        (Adjacent::N, Moves::SW) | (Adjacent::NE, Moves::D) => {
+            adj[i] = Adjacent::N;
+            cur[i] = Location(cur[i].0 - 1, cur[i].1 - 1);
+            process(i + 1, adj, &Moves::SW, cur, tail);
+        }
+
+        (Adjacent::SE, Moves::NE) | (Adjacent::SW, Moves::NW) => {
+            adj[i] = Adjacent::S;
+            cur[i] = Location(cur[i].0, cur[i].1 + 1);
+            process(i + 1, adj, &Moves::U, cur, tail);
+        }
+        (Adjacent::SE, Moves::NW) => {
+            adj[i] = Adjacent::SE;
+            cur[i] = Location(cur[i].0 - 1, cur[i].1 + 1);
+            process(i + 1, adj, &Moves::NW, cur, tail);
+        }
+        (Adjacent::SE, Moves::SW) | (Adjacent::NE, Moves::NW) => {
+            adj[i] = Adjacent::E;
+            cur[i] = Location(cur[i].0 - 1, cur[i].1);
+            process(i + 1, adj, &Moves::L, cur, tail);
+        }
+
+        (Adjacent::SW, Moves::NE) => {
+            adj[i] = Adjacent::SW;
+            cur[i] = Location(cur[i].0 + 1, cur[i].1 + 1);
+            process(i + 1, adj, &Moves::NE, cur, tail);
+        }
+        (Adjacent::SW, Moves::SE) | (Adjacent::NW, Moves::NE) => {
+            adj[i] = Adjacent::W;
+            cur[i] = Location(cur[i].0 + 1, cur[i].1);
+            process(i + 1, adj, &Moves::R, cur, tail);
+        }
+
+        (Adjacent::NE, Moves::SE) | (Adjacent::NW, Moves::SW) => {
+            adj[i] = Adjacent::N;
+            cur[i] = Location(cur[i].0, cur[i].1 - 1);
+            process(i + 1, adj, &Moves::D, cur, tail);
+        }
+        (Adjacent::NE, Moves::SW) => {
+            adj[i] = Adjacent::NE;
+            cur[i] = Location(cur[i].0 - 1, cur[i].1 - 1);
+            process(i + 1, adj, &Moves::SW, cur, tail);
+        }
+
+        (Adjacent::NW, Moves::SE) => {
+            adj[i] = Adjacent::NW;
+            cur[i] = Location(cur[i].0 + 1, cur[i].1 - 1);
+            process(i + 1, adj, &Moves::SE, cur, tail);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE2: &str = include_str!("../example2.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&parse(EXAMPLE)?)?, 13);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&parse(EXAMPLE)?)?, 1);
+        assert_eq!(part2(&parse(EXAMPLE2)?)?, 36);
+        Ok(())
+    }
+
+    #[test]
+    fn no_knots() -> Result<()> {
+        assert!(visited(&parse(EXAMPLE)?, 0).unwrap_err().is::<NoAnswer>());
+        Ok(())
+    }
+}